@@ -0,0 +1,430 @@
+use std::{
+    io::{Read, Write},
+    rc::Rc,
+};
+
+use super::validation::SOURCE_FORMATS;
+
+const BLOCK: usize = 512;
+
+#[derive(thiserror::Error, Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub enum ArchiveError {
+    #[error("The archive is not compressed in any of the accepted formats.")]
+    UnknownFormat,
+    #[error("{0} archives cannot be opened in the browser; download the archive instead.")]
+    Unsupported(&'static str),
+    #[error("The {0} stream is corrupt: {1}")]
+    Corrupt(&'static str, String),
+    #[error("The decompressed file is not a tar archive.")]
+    NotTar,
+    #[error("The archive expands to more than {0} bytes; download the archive instead.")]
+    TooLarge(u64),
+}
+
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum EntryKind {
+    File,
+    Directory,
+    Link,
+}
+
+#[derive(PartialEq)]
+pub struct Entry {
+    pub path: Rc<str>,
+    pub kind: EntryKind,
+    start: usize,
+    pub size: usize,
+}
+
+#[derive(PartialEq)]
+pub struct Archive {
+    data: Vec<u8>,
+    pub entries: Vec<Entry>,
+}
+
+struct Capped {
+    data: Vec<u8>,
+    limit: u64,
+    exceeded: bool,
+}
+
+impl Write for Capped {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if (self.data.len() + buf.len()) as u64 > self.limit {
+            self.exceeded = true;
+            return Err(std::io::Error::other("size limit exceeded"));
+        }
+        self.data.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn format(bytes: &[u8]) -> Result<&'static str, ArchiveError> {
+    SOURCE_FORMATS
+        .iter()
+        .find(|(_, _, magic)| bytes.starts_with(magic))
+        .map(|(_, format, _)| *format)
+        .ok_or(ArchiveError::UnknownFormat)
+}
+
+fn decompress(bytes: &[u8], limit: u64) -> Result<Vec<u8>, ArchiveError> {
+    let format = format(bytes)?;
+    let corrupt = |e: &dyn std::fmt::Display| ArchiveError::Corrupt(format, e.to_string());
+    let mut data = Vec::new();
+    match format {
+        "gzip" => {
+            flate2::read::MultiGzDecoder::new(bytes)
+                .take(limit + 1)
+                .read_to_end(&mut data)
+                .map_err(|e| corrupt(&e))?;
+        }
+        "bzip2" => {
+            bzip2::read::MultiBzDecoder::new(bytes)
+                .take(limit + 1)
+                .read_to_end(&mut data)
+                .map_err(|e| corrupt(&e))?;
+        }
+        "xz" => {
+            let mut capped = Capped { data, limit, exceeded: false };
+            let res = lzma_rs::xz_decompress(&mut std::io::BufReader::new(bytes), &mut capped);
+            if capped.exceeded {
+                return Err(ArchiveError::TooLarge(limit));
+            }
+            res.map_err(|e| corrupt(&e))?;
+            data = capped.data;
+        }
+        "zstd" => {
+            ruzstd::decoding::StreamingDecoder::new(bytes)
+                .map_err(|e| corrupt(&e))?
+                .take(limit + 1)
+                .read_to_end(&mut data)
+                .map_err(|e| corrupt(&e))?;
+        }
+        format => return Err(ArchiveError::Unsupported(format)),
+    }
+    if data.len() as u64 > limit {
+        return Err(ArchiveError::TooLarge(limit));
+    }
+    Ok(data)
+}
+
+fn first_lzma2_chunk(bytes: &[u8]) -> Option<&[u8]> {
+    let block = 12;
+    let chunk = block + (*bytes.get(block)? as usize + 1) * 4;
+    let size = |at: usize| Some(u16::from_be_bytes([*bytes.get(at)?, *bytes.get(at + 1)?]) as usize + 1);
+    let len = match *bytes.get(chunk)? {
+        1 | 2 => 3 + size(chunk + 1)?,
+        status @ 0x80.. => (if status >= 0xc0 { 6 } else { 5 }) + size(chunk + 3)?,
+        _ => return None,
+    };
+    bytes.get(chunk..chunk + len)
+}
+
+fn decompress_head(bytes: &[u8]) -> Result<Vec<u8>, ArchiveError> {
+    let format = format(bytes)?;
+    let corrupt = |e: &dyn std::fmt::Display| ArchiveError::Corrupt(format, e.to_string());
+    let mut data = Vec::new();
+    match format {
+        "gzip" => {
+            flate2::read::MultiGzDecoder::new(bytes)
+                .take(BLOCK as u64)
+                .read_to_end(&mut data)
+                .map_err(|e| corrupt(&e))?;
+        }
+        "bzip2" => {
+            bzip2::read::MultiBzDecoder::new(bytes)
+                .take(BLOCK as u64)
+                .read_to_end(&mut data)
+                .map_err(|e| corrupt(&e))?;
+        }
+        "xz" => {
+            let chunk = first_lzma2_chunk(bytes).ok_or_else(|| corrupt(&"the first block is truncated"))?;
+            lzma_rs::lzma2_decompress(&mut [chunk, &[0]].concat().as_slice(), &mut data).map_err(|e| corrupt(&e))?;
+        }
+        "zstd" => {
+            ruzstd::decoding::StreamingDecoder::new(bytes)
+                .map_err(|e| corrupt(&e))?
+                .take(BLOCK as u64)
+                .read_to_end(&mut data)
+                .map_err(|e| corrupt(&e))?;
+        }
+        format => return Err(ArchiveError::Unsupported(format)),
+    }
+    Ok(data)
+}
+
+pub fn check_head(bytes: &[u8]) -> Result<(), ArchiveError> {
+    match decompress_head(bytes)?.get(..BLOCK) {
+        Some(header) if is_header(header) => Ok(()),
+        _ => Err(ArchiveError::NotTar),
+    }
+}
+
+fn is_header(header: &[u8]) -> bool {
+    let sum = header
+        .iter()
+        .enumerate()
+        .map(|(i, b)| if (148..156).contains(&i) { b' ' as usize } else { *b as usize })
+        .sum::<usize>();
+    octal(header, 148, 8) == Some(sum)
+}
+
+fn field(header: &[u8], start: usize, len: usize) -> &str {
+    let raw = &header[start..start + len];
+    let end = raw.iter().position(|b| *b == 0).unwrap_or(len);
+    std::str::from_utf8(&raw[..end]).unwrap_or_default()
+}
+
+fn octal(header: &[u8], start: usize, len: usize) -> Option<usize> {
+    usize::from_str_radix(field(header, start, len).trim_matches(|c: char| c == ' ' || c == '\0'), 8).ok()
+}
+
+fn pax_path(records: &[u8]) -> Option<String> {
+    String::from_utf8_lossy(records)
+        .lines()
+        .filter_map(|line| line.split_once(' ').map(|(_, record)| record))
+        .find_map(|record| record.strip_prefix("path=").map(str::to_owned))
+}
+
+impl Archive {
+    pub fn open(bytes: &[u8], limit: u64) -> Result<Self, ArchiveError> {
+        let data = decompress(bytes, limit)?;
+        let mut entries = Vec::new();
+        let mut long_name = None;
+        let mut offset = 0;
+        while offset + BLOCK <= data.len() {
+            let header = &data[offset..offset + BLOCK];
+            if header.iter().all(|b| *b == 0) {
+                break;
+            }
+            if !is_header(header) {
+                return Err(ArchiveError::NotTar);
+            }
+            let size = octal(header, 124, 12).unwrap_or_default();
+            let start = offset + BLOCK;
+            let end = start.checked_add(size).ok_or(ArchiveError::NotTar)?.min(data.len());
+            offset = size
+                .div_ceil(BLOCK)
+                .checked_mul(BLOCK)
+                .and_then(|padded| start.checked_add(padded))
+                .ok_or(ArchiveError::NotTar)?;
+
+            let kind = match header[156] {
+                b'L' => {
+                    long_name = Some(String::from_utf8_lossy(&data[start..end]).trim_end_matches('\0').to_owned());
+                    continue;
+                }
+                b'x' => {
+                    long_name = pax_path(&data[start..end]);
+                    continue;
+                }
+                b'g' | b'K' => continue,
+                b'5' => EntryKind::Directory,
+                b'1' | b'2' => EntryKind::Link,
+                _ => EntryKind::File,
+            };
+            let path = long_name.take().unwrap_or_else(|| {
+                let name = field(header, 0, 100);
+                match field(header, 345, 155) {
+                    prefix if !prefix.is_empty() && &header[257..262] == b"ustar" => format!("{prefix}/{name}"),
+                    _ => name.to_owned(),
+                }
+            });
+            let path = path.trim_start_matches("./").trim_end_matches('/');
+            if path.is_empty() || path == "." {
+                continue;
+            }
+            entries.push(Entry {
+                path: path.into(),
+                kind,
+                start,
+                size: if kind == EntryKind::File { end - start } else { 0 },
+            });
+        }
+        if entries.is_empty() && !data.is_empty() && data.iter().any(|b| *b != 0) {
+            return Err(ArchiveError::NotTar);
+        }
+        Ok(Self { data, entries })
+    }
+
+    pub fn content(&self, entry: &Entry) -> &[u8] {
+        &self.data[entry.start..entry.start + entry.size]
+    }
+}
+
+fn push_entry(data: &mut Vec<u8>, path: &str, kind: u8, content: &[u8], mtime: i64) {
+    let mut header = [0u8; BLOCK];
+    let name = &path.as_bytes()[..path.len().min(99)];
+    header[..name.len()].copy_from_slice(name);
+    header[100..108].copy_from_slice(b"0000644\0");
+    header[108..116].copy_from_slice(b"0000000\0");
+    header[116..124].copy_from_slice(b"0000000\0");
+    header[124..136].copy_from_slice(format!("{:011o}\0", content.len()).as_bytes());
+    header[136..148].copy_from_slice(format!("{:011o}\0", mtime.max(0)).as_bytes());
+    header[156] = kind;
+    header[257..265].copy_from_slice(b"ustar\x0000");
+    header[148..156].fill(b' ');
+    let sum = header.iter().map(|b| *b as usize).sum::<usize>();
+    header[148..156].copy_from_slice(format!("{sum:06o}\0 ").as_bytes());
+    data.extend_from_slice(&header);
+    data.extend_from_slice(content);
+    data.resize(data.len().div_ceil(BLOCK) * BLOCK, 0);
+}
+
+pub fn pack<'a>(files: impl IntoIterator<Item = (&'a str, &'a [u8])>, mtime: i64) -> Vec<u8> {
+    let mut data = Vec::new();
+    for (path, content) in files {
+        if path.len() >= 100 {
+            push_entry(&mut data, "././@LongLink", b'L', format!("{path}\0").as_bytes(), mtime);
+        }
+        push_entry(&mut data, path, b'0', content, mtime);
+    }
+    data.resize(data.len() + 2 * BLOCK, 0);
+    data
+}
+
+pub fn extension(bytes: &[u8]) -> &'static str {
+    SOURCE_FORMATS
+        .iter()
+        .find(|(_, _, magic)| bytes.starts_with(magic))
+        .map(|(ext, _, _)| *ext)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn paths(archive: &Archive) -> Vec<&str> {
+        archive.entries.iter().map(|e| &*e.path).collect()
+    }
+
+    #[test]
+    fn pack_round_trips() {
+        let tar = pack([("thesis/main.tex", &b"\\documentclass{article}"[..]), ("thesis/refs.bib", &b""[..])], 0);
+        let archive = Archive::open(&gzip(&tar), 1 << 20).unwrap();
+        assert_eq!(paths(&archive), ["thesis/main.tex", "thesis/refs.bib"]);
+        assert_eq!(archive.content(&archive.entries[0]), b"\\documentclass{article}");
+        assert_eq!(archive.entries[1].size, 0);
+    }
+
+    #[test]
+    fn long_names_use_gnu_long_link() {
+        let long = format!("{}/figure.pdf", "chapter".repeat(20));
+        let tar = pack([(long.as_str(), &b"%PDF-1.7"[..])], 0);
+        let archive = Archive::open(&gzip(&tar), 1 << 20).unwrap();
+        assert_eq!(paths(&archive), [long.as_str()]);
+        assert_eq!(archive.content(&archive.entries[0]), b"%PDF-1.7");
+    }
+
+    #[test]
+    fn pax_path_overrides_header_name() {
+        let record = "path=sources/very/deep/main.tex\n";
+        let record = format!("{} {record}", record.len() + 3);
+        let mut tar = Vec::new();
+        push_entry(&mut tar, "PaxHeaders/main.tex", b'x', record.as_bytes(), 0);
+        push_entry(&mut tar, "main.tex", b'0', b"hello", 0);
+        tar.resize(tar.len() + 2 * BLOCK, 0);
+        let archive = Archive::open(&gzip(&tar), 1 << 20).unwrap();
+        assert_eq!(paths(&archive), ["sources/very/deep/main.tex"]);
+    }
+
+    #[test]
+    fn long_link_targets_are_skipped() {
+        let mut tar = Vec::new();
+        push_entry(&mut tar, "././@LongLink", b'K', b"target\0", 0);
+        push_entry(&mut tar, "link", b'2', b"", 0);
+        tar.resize(tar.len() + 2 * BLOCK, 0);
+        let archive = Archive::open(&gzip(&tar), 1 << 20).unwrap();
+        assert_eq!(paths(&archive), ["link"]);
+        assert!(archive.entries[0].kind == EntryKind::Link);
+    }
+
+    #[test]
+    fn all_zero_tail_ends_the_archive() {
+        let mut tar = pack([("a.txt", &b"a"[..])], 0);
+        tar.resize(tar.len() + 8 * BLOCK, 0);
+        let archive = Archive::open(&gzip(&tar), 1 << 20).unwrap();
+        assert_eq!(paths(&archive), ["a.txt"]);
+
+        let empty = Archive::open(&gzip(&[0; 2 * BLOCK]), 1 << 20).unwrap();
+        assert!(empty.entries.is_empty());
+    }
+
+    #[test]
+    fn bad_checksum_is_not_tar() {
+        let mut tar = pack([("a.txt", &b"a"[..])], 0);
+        tar[0] = b'b';
+        assert!(Archive::open(&gzip(&tar), 1 << 20) == Err(ArchiveError::NotTar));
+    }
+
+    #[test]
+    fn size_past_the_end_does_not_panic() {
+        let mut tar = Vec::new();
+        push_entry(&mut tar, "huge", b'0', b"", 0);
+        tar[124..136].copy_from_slice(b"77777777777\0");
+        tar[148..156].fill(b' ');
+        let sum = tar[..BLOCK].iter().map(|b| *b as usize).sum::<usize>();
+        tar[148..156].copy_from_slice(format!("{sum:06o}\0 ").as_bytes());
+        let archive = Archive::open(&gzip(&tar), 1 << 20).unwrap();
+        assert_eq!(paths(&archive), ["huge"]);
+        assert_eq!(archive.entries[0].size, 0);
+    }
+
+    #[test]
+    fn expansion_is_capped() {
+        let tar = pack([("zeros", &[0; 4 * BLOCK][..])], 0);
+        assert!(Archive::open(&gzip(&tar), 1024) == Err(ArchiveError::TooLarge(1024)));
+    }
+
+    #[test]
+    fn head_of_compressed_tar_is_checked() {
+        let content = (0..1u32 << 18).map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8).collect::<Vec<_>>();
+        let gz = gzip(&pack([("main.tex", content.as_slice())], 0));
+        assert_eq!(check_head(&gz), Ok(()));
+        assert_eq!(check_head(&gz[..gz.len() / 4]), Ok(()));
+        assert_eq!(check_head(&gzip(b"%PDF-1.7\n%%EOF\n")), Err(ArchiveError::NotTar));
+        assert_eq!(check_head(&gzip(&[0; 1024])), Err(ArchiveError::NotTar));
+        assert!(matches!(check_head(b"\x1f\x8bgarbage"), Err(ArchiveError::Corrupt("gzip", _))));
+    }
+
+    #[test]
+    fn head_of_xz_tar_is_read_from_the_first_chunk() {
+        const XZ: &str = "fd377a585a0000016922de3604c07d80502101160000000000000000c330780ae027ff00755d00369849728502d2e6\
+            106cfc7e9eb95d9b6f492d09ed7486317b6ad95038fb5b7333373d4d121380e622764eb8ae7bc96f083e38e1799982ee0127a52466039\
+            bc1070d006b688ce91d00263bd0cc1d9b3f091d876bca58d79100fce5de1f75960433f3457c50f9a009cb8ca8989b46c1795cc54b1000\
+            0000000031495c1d000195018050000016e27b323e300d8b020000000001595a";
+        let xz = XZ
+            .split_whitespace()
+            .collect::<String>()
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(check_head(&xz), Ok(()));
+        assert_eq!(check_head(&xz[..160]), Ok(()));
+        assert!(matches!(check_head(&xz[..40]), Err(ArchiveError::Corrupt("xz", _))));
+    }
+
+    #[test]
+    fn unknown_compression_is_rejected() {
+        assert!(Archive::open(b"plain text", 1 << 20) == Err(ArchiveError::UnknownFormat));
+        assert!(Archive::open(b"LZIP\x01", 1 << 20) == Err(ArchiveError::Unsupported("lzip")));
+        assert_eq!(extension(b"\xfd7zXZ\x00rest"), ".tar.xz");
+    }
+}
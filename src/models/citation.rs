@@ -0,0 +1,501 @@
+use std::rc::Rc;
+
+use chrono::Datelike;
+
+use super::{
+    common::{AppConfig, AppResult, FetchRes},
+    doi,
+    profile::PublicProfile,
+    thesis::Thesis,
+    version::Version,
+};
+
+const PUBLISHER: &str = "Prepublish";
+
+const MLA_MONTHS: [&str; 12] = [
+    "Jan.", "Feb.", "Mar.", "Apr.", "May", "June", "July", "Aug.", "Sept.", "Oct.", "Nov.", "Dec.",
+];
+
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum Style {
+    BibTeX,
+    Ris,
+    CslJson,
+    Apa,
+    Mla,
+    Chicago,
+}
+
+impl Style {
+    pub const ALL: [Style; 6] = [
+        Style::BibTeX,
+        Style::Ris,
+        Style::CslJson,
+        Style::Apa,
+        Style::Mla,
+        Style::Chicago,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Style::BibTeX => "BibTeX",
+            Style::Ris => "RIS",
+            Style::CslJson => "CSL-JSON",
+            Style::Apa => "APA",
+            Style::Mla => "MLA",
+            Style::Chicago => "Chicago",
+        }
+    }
+
+    pub fn file(self) -> Option<(&'static str, &'static str)> {
+        match self {
+            Style::BibTeX => Some(("bib", "application/x-bibtex")),
+            Style::Ris => Some(("ris", "application/x-research-info-systems")),
+            Style::CslJson => Some(("json", "application/vnd.citationstyles.csl+json")),
+            _ => None,
+        }
+    }
+}
+
+pub struct Citable<'a> {
+    pub thesis: &'a Thesis,
+    pub authors: &'a [Rc<PublicProfile>],
+    pub version: Option<&'a Version>,
+    pub url: &'a str,
+}
+
+pub struct Sources {
+    pub authors: Vec<Rc<PublicProfile>>,
+    pub version: Option<Rc<Version>>,
+}
+
+impl Sources {
+    pub async fn try_load_authors(cfg: &AppConfig, thesis: &Thesis) -> AppResult<Vec<Rc<PublicProfile>>> {
+        let mut authors = Vec::with_capacity(thesis.author_ids.len());
+        for author_id in thesis.author_ids.iter() {
+            if let FetchRes::Body(author) = PublicProfile::try_get(cfg, *author_id).await? {
+                authors.push(author);
+            }
+        }
+        Ok(authors)
+    }
+
+    pub async fn try_load(cfg: &AppConfig, thesis: &Thesis) -> AppResult<Self> {
+        let authors = Self::try_load_authors(cfg, thesis).await?;
+        let version = match Version::try_list(cfg, &[("thesis_id", &thesis.id._id.to_hex())]).await? {
+            FetchRes::Body(versions) => Version::latest(&versions).cloned(),
+            FetchRes::Other(_) => None,
+        };
+        Ok(Self { authors, version })
+    }
+}
+
+fn split_name(name: &str) -> (&str, &str) {
+    let name = name.trim();
+    match name.rsplit_once(char::is_whitespace) {
+        Some((given, family)) => (family, given.trim()),
+        None => (name, ""),
+    }
+}
+
+fn initials(given: &str) -> String {
+    given
+        .split_whitespace()
+        .filter_map(|g| g.chars().next())
+        .map(|c| format!("{c}."))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn escape_bibtex(val: &str) -> String {
+    let mut escaped = String::with_capacity(val.len());
+    for c in val.chars() {
+        match c {
+            '{' | '}' | '&' | '%' | '$' | '#' | '_' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+impl<'a> Citable<'a> {
+    pub fn render(&self, style: Style) -> String {
+        match style {
+            Style::BibTeX => self.bibtex(),
+            Style::Ris => self.ris(),
+            Style::CslJson => self.csl_json(),
+            Style::Apa => self.apa(),
+            Style::Mla => self.mla(),
+            Style::Chicago => self.chicago(),
+        }
+    }
+
+    fn link(&self) -> String {
+        match self.thesis.doi {
+            Some(ref doi) => doi::url(doi),
+            None => self.url.to_owned(),
+        }
+    }
+
+    fn key(&self) -> String {
+        let author = self
+            .authors
+            .first()
+            .map(|a| split_name(&a.name).0)
+            .unwrap_or("anonymous");
+        let word = self
+            .thesis
+            .title
+            .split_whitespace()
+            .find(|w| w.len() > 3)
+            .unwrap_or_default();
+        format!("{}{}{}", author, self.thesis.id.created_at.year(), word)
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    }
+
+    pub fn bibtex(&self) -> String {
+        let thesis = self.thesis;
+        let mut fields = vec![
+            ("title", format!("{{{}}}", escape_bibtex(&thesis.title))),
+            (
+                "author",
+                self.authors
+                    .iter()
+                    .map(|a| escape_bibtex(&a.name))
+                    .collect::<Vec<_>>()
+                    .join(" and "),
+            ),
+            ("year", thesis.id.created_at.year().to_string()),
+            ("month", thesis.id.created_at.format("%b").to_string().to_lowercase()),
+            ("abstract", escape_bibtex(&thesis.abstraction)),
+        ];
+        if !thesis.keywords.is_empty() {
+            fields.push((
+                "keywords",
+                thesis.keywords.iter().map(|k| escape_bibtex(k)).collect::<Vec<_>>().join(", "),
+            ));
+        }
+        if let Some(ref doi) = thesis.doi {
+            fields.push(("doi", escape_bibtex(doi)));
+        }
+        fields.push(("note", "Preprint".to_owned()));
+        fields.push(("url", self.url.to_owned()));
+        if let Some(version) = self.version {
+            fields.push(("version", format!("{}.{}", version.major_num, version.minor_num)));
+        }
+        let fields = fields
+            .into_iter()
+            .map(|(k, v)| match k {
+                "month" => format!("  {k} = {v}"),
+                _ => format!("  {k} = {{{v}}}"),
+            })
+            .collect::<Vec<_>>()
+            .join(",\n");
+        format!("@unpublished{{{},\n{}\n}}\n", self.key(), fields)
+    }
+
+    pub fn ris(&self) -> String {
+        let thesis = self.thesis;
+        let mut lines = vec![("TY", "UNPB".to_owned()), ("TI", thesis.title.to_string())];
+        for author in self.authors {
+            let (family, given) = split_name(&author.name);
+            lines.push(("AU", if given.is_empty() { family.to_owned() } else { format!("{family}, {given}") }));
+        }
+        lines.push(("PY", thesis.id.created_at.format("%Y/%m/%d/").to_string()));
+        lines.push(("AB", thesis.abstraction.replace('\n', " ")));
+        for keyword in thesis.keywords.iter() {
+            lines.push(("KW", keyword.to_string()));
+        }
+        for language in thesis.languages.iter() {
+            lines.push(("LA", language.to_string()));
+        }
+        if let Some(ref doi) = thesis.doi {
+            lines.push(("DO", doi.to_string()));
+        }
+        if let Some(version) = self.version {
+            lines.push(("ET", format!("{}.{}", version.major_num, version.minor_num)));
+        }
+        lines.push(("PB", PUBLISHER.to_owned()));
+        lines.push(("UR", self.url.to_owned()));
+        lines.push(("ER", String::new()));
+        lines
+            .into_iter()
+            .map(|(tag, val)| format!("{tag}  - {val}"))
+            .collect::<Vec<_>>()
+            .join("\r\n")
+    }
+
+    pub fn csl_json(&self) -> String {
+        let thesis = self.thesis;
+        let created_at = thesis.id.created_at;
+        let mut item = serde_json::json!({
+            "id": self.key(),
+            "type": "article",
+            "genre": "Preprint",
+            "title": thesis.title,
+            "abstract": thesis.abstraction,
+            "author": self.authors.iter().map(|a| {
+                let (family, given) = split_name(&a.name);
+                serde_json::json!({ "family": family, "given": given })
+            }).collect::<Vec<_>>(),
+            "issued": { "date-parts": [[created_at.year(), created_at.month(), created_at.day()]] },
+            "publisher": PUBLISHER,
+            "URL": self.url,
+        });
+        if let Some(ref doi) = thesis.doi {
+            item["DOI"] = serde_json::json!(doi);
+        }
+        if !thesis.keywords.is_empty() {
+            item["keyword"] = serde_json::json!(thesis.keywords.join(", "));
+        }
+        if let Some(language) = thesis.languages.iter().next() {
+            item["language"] = serde_json::json!(language);
+        }
+        if let Some(version) = self.version {
+            item["version"] = serde_json::json!(format!("{}.{}", version.major_num, version.minor_num));
+        }
+        serde_json::to_string_pretty(&serde_json::json!([item])).unwrap_or_default()
+    }
+
+    pub fn apa(&self) -> String {
+        let names = self
+            .authors
+            .iter()
+            .map(|a| {
+                let (family, given) = split_name(&a.name);
+                if given.is_empty() {
+                    family.to_owned()
+                } else {
+                    format!("{}, {}", family, initials(given))
+                }
+            })
+            .collect::<Vec<_>>();
+        let authors = match names.as_slice() {
+            [] => String::new(),
+            [one] => one.clone(),
+            [init @ .., last] => format!("{}, & {}", init.join(", "), last),
+        };
+        format!(
+            "{} ({}). {} [Preprint]. {}. {}",
+            authors,
+            self.thesis.id.created_at.year(),
+            self.thesis.title,
+            PUBLISHER,
+            self.link(),
+        )
+    }
+
+    pub fn mla(&self) -> String {
+        let created_at = self.thesis.id.created_at;
+        let date = format!("{} {} {}", created_at.day(), MLA_MONTHS[created_at.month0() as usize], created_at.year());
+        let inverted = |a: &PublicProfile| {
+            let (family, given) = split_name(&a.name);
+            if given.is_empty() {
+                family.to_owned()
+            } else {
+                format!("{family}, {given}")
+            }
+        };
+        let authors = match self.authors {
+            [] => String::new(),
+            [one] => inverted(one),
+            [first, second] => format!("{}, and {}", inverted(first), second.name),
+            [first, ..] => format!("{}, et al", inverted(first)),
+        };
+        format!(
+            "{}. \u{201c}{}.\u{201d} {}, {}, {}.",
+            authors.trim_end_matches('.'),
+            self.thesis.title,
+            PUBLISHER,
+            date,
+            self.link(),
+        )
+    }
+
+    pub fn chicago(&self) -> String {
+        let names = self
+            .authors
+            .iter()
+            .enumerate()
+            .map(|(i, a)| {
+                let (family, given) = split_name(&a.name);
+                if i == 0 && !given.is_empty() {
+                    format!("{family}, {given}")
+                } else {
+                    a.name.to_string()
+                }
+            })
+            .collect::<Vec<_>>();
+        let authors = match names.as_slice() {
+            [] => String::new(),
+            [one] => one.clone(),
+            [init @ .., last] => format!("{}, and {}", init.join(", "), last),
+        };
+        format!(
+            "{}. \u{201c}{}.\u{201d} Preprint, {}, {}. {}.",
+            authors.trim_end_matches('.'),
+            self.thesis.title,
+            PUBLISHER,
+            self.thesis.id.created_at.format("%B %-d, %Y"),
+            self.link(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    const URL: &str = "https://example.org/theses/1";
+
+    fn fixture(title: &str, (y, m, d): (i32, u32, u32)) -> Thesis {
+        let mut thesis = Thesis {
+            title: title.into(),
+            abstraction: "First line\nsecond line".into(),
+            keywords: Rc::new(vec!["graphs".into(), "50%".into()]),
+            ..Default::default()
+        };
+        thesis.id.created_at = Utc.with_ymd_and_hms(y, m, d, 12, 0, 0).unwrap();
+        thesis
+    }
+
+    fn authors(names: &[&str]) -> Vec<Rc<PublicProfile>> {
+        names
+            .iter()
+            .map(|name| {
+                Rc::new(PublicProfile {
+                    _id: Default::default(),
+                    email: "".into(),
+                    avatar_id: None,
+                    joining_at: Default::default(),
+                    name: (*name).into(),
+                })
+            })
+            .collect()
+    }
+
+    fn cite(thesis: &Thesis, authors: &[Rc<PublicProfile>], style: Style) -> String {
+        Citable { thesis, authors, version: None, url: URL }.render(style)
+    }
+
+    #[test]
+    fn names_split_on_the_last_word() {
+        assert_eq!(split_name(" Alan Mathison Turing "), ("Turing", "Alan Mathison"));
+        assert_eq!(split_name("Plato"), ("Plato", ""));
+        assert_eq!(initials("Alan Mathison"), "A. M.");
+    }
+
+    #[test]
+    fn bibtex_escapes_and_keys() {
+        let thesis = fixture("Cost & 50% of A_B {graphs}", (2024, 5, 3));
+        let authors = authors(&["Ada Lovelace", "Alan Turing"]);
+        let citable = Citable { thesis: &thesis, authors: &authors, version: None, url: URL };
+        assert_eq!(citable.key(), "lovelace2024cost");
+        assert_eq!(escape_bibtex(r"a\b~c^d#e$f"), r"a\textbackslash{}b\textasciitilde{}c\textasciicircum{}d\#e\$f");
+        let bibtex = citable.bibtex();
+        assert!(bibtex.starts_with("@unpublished{lovelace2024cost,\n"));
+        assert!(bibtex.contains("  title = {{Cost \\& 50\\% of A\\_B \\{graphs\\}}},\n"));
+        assert!(bibtex.contains("  author = {Ada Lovelace and Alan Turing},\n"));
+        assert!(bibtex.contains("  month = may,\n"));
+        assert!(bibtex.contains("  keywords = {graphs, 50\\%},\n"));
+        assert!(bibtex.ends_with("  url = {https://example.org/theses/1}\n}\n"));
+    }
+
+    #[test]
+    fn bibtex_key_without_authors() {
+        let thesis = fixture("On it", (2024, 5, 3));
+        assert!(cite(&thesis, &[], Style::BibTeX).starts_with("@unpublished{anonymous2024,\n"));
+    }
+
+    #[test]
+    fn ris_lists_authors_and_ends_with_er() {
+        let thesis = fixture("Graphs", (2024, 5, 3));
+        let ris = cite(&thesis, &authors(&["Ada Lovelace", "Plato", "Grace Brewster Hopper"]), Style::Ris);
+        let lines = ris.split("\r\n").collect::<Vec<_>>();
+        assert_eq!(lines.first(), Some(&"TY  - UNPB"));
+        assert_eq!(lines.last(), Some(&"ER  - "));
+        assert!(lines.contains(&"AU  - Lovelace, Ada"));
+        assert!(lines.contains(&"AU  - Plato"));
+        assert!(lines.contains(&"AU  - Hopper, Grace Brewster"));
+        assert!(lines.contains(&"PY  - 2024/05/03/"));
+        assert!(lines.contains(&"AB  - First line second line"));
+        assert!(!ris.replace("\r\n", "").contains('\n'));
+    }
+
+    #[test]
+    fn csl_json_splits_names_and_dates() {
+        let thesis = fixture("Graphs", (2024, 5, 3));
+        let json = cite(&thesis, &authors(&["Ada Lovelace", "Plato"]), Style::CslJson);
+        let items = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+        let item = &items[0];
+        assert_eq!(item["author"][0], serde_json::json!({ "family": "Lovelace", "given": "Ada" }));
+        assert_eq!(item["author"][1], serde_json::json!({ "family": "Plato", "given": "" }));
+        assert_eq!(item["issued"]["date-parts"], serde_json::json!([[2024, 5, 3]]));
+        assert_eq!(item["keyword"], "graphs, 50%");
+        assert_eq!(item["URL"], URL);
+    }
+
+    #[test]
+    fn apa_joins_authors() {
+        let thesis = fixture("Graphs", (2024, 5, 3));
+        let tail = "(2024). Graphs [Preprint]. Prepublish. https://example.org/theses/1";
+        assert_eq!(cite(&thesis, &authors(&["Ada Lovelace"]), Style::Apa), format!("Lovelace, A. {tail}"));
+        assert_eq!(cite(&thesis, &authors(&["Plato"]), Style::Apa), format!("Plato {tail}"));
+        assert_eq!(
+            cite(&thesis, &authors(&["Ada Lovelace", "Alan Mathison Turing"]), Style::Apa),
+            format!("Lovelace, A., & Turing, A. M. {tail}")
+        );
+        assert_eq!(
+            cite(&thesis, &authors(&["Ada Lovelace", "Alan Turing", "Grace Hopper"]), Style::Apa),
+            format!("Lovelace, A., Turing, A., & Hopper, G. {tail}")
+        );
+    }
+
+    #[test]
+    fn mla_joins_authors_and_spells_months() {
+        let thesis = fixture("Graphs", (2024, 5, 3));
+        let tail = "\u{201c}Graphs.\u{201d} Prepublish, 3 May 2024, https://example.org/theses/1.";
+        assert_eq!(cite(&thesis, &authors(&["Ada Lovelace"]), Style::Mla), format!("Lovelace, Ada. {tail}"));
+        assert_eq!(cite(&thesis, &authors(&["Plato"]), Style::Mla), format!("Plato. {tail}"));
+        assert_eq!(
+            cite(&thesis, &authors(&["Ada Lovelace", "Alan Turing"]), Style::Mla),
+            format!("Lovelace, Ada, and Alan Turing. {tail}")
+        );
+        assert_eq!(
+            cite(&thesis, &authors(&["Ada Lovelace", "Alan Turing", "Grace Hopper"]), Style::Mla),
+            format!("Lovelace, Ada, et al. {tail}")
+        );
+        let dates = [((6, 1), "1 June 2024"), ((7, 14), "14 July 2024"), ((9, 30), "30 Sept. 2024"), ((12, 9), "9 Dec. 2024")];
+        for ((m, d), date) in dates {
+            assert!(cite(&fixture("Graphs", (2024, m, d)), &[], Style::Mla).contains(date));
+        }
+    }
+
+    #[test]
+    fn chicago_joins_authors() {
+        let thesis = fixture("Graphs", (2024, 5, 3));
+        let tail = "\u{201c}Graphs.\u{201d} Preprint, Prepublish, May 3, 2024. https://example.org/theses/1.";
+        assert_eq!(cite(&thesis, &authors(&["Ada Lovelace"]), Style::Chicago), format!("Lovelace, Ada. {tail}"));
+        assert_eq!(cite(&thesis, &authors(&["Plato"]), Style::Chicago), format!("Plato. {tail}"));
+        assert_eq!(
+            cite(&thesis, &authors(&["Ada Lovelace", "Alan Turing"]), Style::Chicago),
+            format!("Lovelace, Ada, and Alan Turing. {tail}")
+        );
+        assert_eq!(
+            cite(&thesis, &authors(&["Ada Lovelace", "Alan Turing", "Grace Hopper"]), Style::Chicago),
+            format!("Lovelace, Ada, Alan Turing, and Grace Hopper. {tail}")
+        );
+    }
+}
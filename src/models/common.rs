@@ -26,6 +26,34 @@ pub type AppResult<T> = Result<T, AppError>;
 #[derive(Clone)]
 pub struct AppConfig {
     pub api: std::rc::Rc<url::Url>,
+    #[serde(default)]
+    pub limits: UploadLimits,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(default)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub struct UploadLimits {
+    pub release: u64,
+    pub source: u64,
+    pub supplement: u64,
+}
+
+impl Default for UploadLimits {
+    fn default() -> Self {
+        Self {
+            release: 50 << 20,
+            source: 200 << 20,
+            supplement: 100 << 20,
+        }
+    }
+}
+
+impl UploadLimits {
+    pub fn extracted(self) -> u64 {
+        self.source.saturating_mul(4)
+    }
 }
 
 impl AppConfig {
@@ -44,6 +72,21 @@ pub struct FetchOther {
     pub msg: yew::AttrValue,
 }
 
+impl FetchOther {
+    pub async fn try_from_empty_res(res: Result<gloo::net::http::Response, gloo::net::Error>) -> AppResult<Option<Self>> {
+        let res = res?;
+        if res.ok() {
+            Ok(None)
+        } else {
+            let msg = res.text().await;
+            Ok(Some(FetchOther {
+                status: res.status(),
+                msg: msg?.into(),
+            }))
+        }
+    }
+}
+
 pub enum FetchRes<T: serde::de::DeserializeOwned> {
     Body(T),
     Other(FetchOther),
@@ -63,4 +106,20 @@ impl<T: serde::de::DeserializeOwned> FetchRes<T> {
             }))
         }
     }
-}
\ No newline at end of file
+}
+
+impl FetchRes<Vec<u8>> {
+    pub async fn try_binary_from_gloo_res(res: Result<gloo::net::http::Response, gloo::net::Error>) -> AppResult<Self> {
+        let res = res?;
+        if res.ok() {
+            let body = res.binary().await;
+            Ok(FetchRes::Body(body?))
+        } else {
+            let msg = res.text().await;
+            Ok(FetchRes::Other(FetchOther {
+                status: res.status(),
+                msg: msg?.into(),
+            }))
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use gloo::file::{futures::read_as_bytes, Blob, File, FileReadError};
+use sha2::{Digest, Sha256};
+
+use super::upload::CHUNK_SIZE;
+
+pub async fn sha256(file: &File) -> Result<String, FileReadError> {
+    let mut hasher = Sha256::new();
+    let mut offset = 0;
+    while offset < file.size() {
+        let end = (offset + CHUNK_SIZE).min(file.size());
+        hasher.update(read_as_bytes(&Blob::from(file.slice(offset, end))).await?);
+        offset = end;
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect())
+}
@@ -0,0 +1,78 @@
+use std::rc::Rc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+const RESOLVER: &str = "https://doi.org/";
+
+#[derive(PartialEq)]
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, Default)]
+pub enum DoiState {
+    #[default]
+    Pending,
+    Registered,
+}
+
+#[derive(PartialEq)]
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+#[derive(Default)]
+pub struct DoiRequest {
+    pub state: DoiState,
+    pub requested_at: Rc<DateTime<Utc>>,
+}
+
+pub fn normalize(input: &str) -> Option<String> {
+    let input = input.trim();
+    let lower = input.to_ascii_lowercase();
+    let doi = ["https://doi.org/", "http://doi.org/", "https://dx.doi.org/", "http://dx.doi.org/", "doi:"]
+        .iter()
+        .find(|prefix| lower.starts_with(*prefix))
+        .map_or(input, |prefix| input[prefix.len()..].trim_start());
+    let (prefix, suffix) = doi.split_once('/')?;
+    let registrant = prefix.strip_prefix("10.")?;
+    let valid_registrant = (4..=9).contains(&registrant.split('.').next()?.len())
+        && registrant
+            .split('.')
+            .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()));
+    let valid_suffix = !suffix.is_empty()
+        && suffix
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-._;()/:<>[]+".contains(c));
+    (valid_registrant && valid_suffix).then(|| doi.to_owned())
+}
+
+pub fn url(doi: &str) -> String {
+    format!("{RESOLVER}{doi}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_resolver_prefixes() {
+        assert_eq!(normalize(" 10.1000/182 ").as_deref(), Some("10.1000/182"));
+        assert_eq!(normalize("doi: 10.1000/182").as_deref(), Some("10.1000/182"));
+        assert_eq!(normalize("https://doi.org/10.1000/182").as_deref(), Some("10.1000/182"));
+        assert_eq!(normalize("HTTP://DX.DOI.ORG/10.1000/182").as_deref(), Some("10.1000/182"));
+    }
+
+    #[test]
+    fn keeps_suffix_case_and_subdivided_registrants() {
+        assert_eq!(normalize("10.1038.01/Nature-12373").as_deref(), Some("10.1038.01/Nature-12373"));
+        assert_eq!(normalize("10.1002/(SICI)1097-4571").as_deref(), Some("10.1002/(SICI)1097-4571"));
+    }
+
+    #[test]
+    fn rejects_malformed_dois() {
+        assert_eq!(normalize(""), None);
+        assert_eq!(normalize("10.1000"), None);
+        assert_eq!(normalize("10.1000/"), None);
+        assert_eq!(normalize("11.1000/182"), None);
+        assert_eq!(normalize("10.12/182"), None);
+        assert_eq!(normalize("10.1000./182"), None);
+        assert_eq!(normalize("10.1000/a b"), None);
+    }
+}
@@ -0,0 +1,237 @@
+pub const COMMON_LANGUAGES: [(&str, &str); 20] = [
+    ("ar", "Arabic"),
+    ("de", "German"),
+    ("en", "English"),
+    ("es", "Spanish"),
+    ("fa", "Persian"),
+    ("fr", "French"),
+    ("hi", "Hindi"),
+    ("id", "Indonesian"),
+    ("it", "Italian"),
+    ("ja", "Japanese"),
+    ("ko", "Korean"),
+    ("nl", "Dutch"),
+    ("pl", "Polish"),
+    ("pt", "Portuguese"),
+    ("ru", "Russian"),
+    ("sv", "Swedish"),
+    ("tr", "Turkish"),
+    ("uk", "Ukrainian"),
+    ("zh-Hans", "Chinese (Simplified)"),
+    ("zh-Hant", "Chinese (Traditional)"),
+];
+
+fn is_alpha(s: &str, min: usize, max: usize) -> bool {
+    (min..=max).contains(&s.len()) && s.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+fn is_alnum(s: &str, min: usize, max: usize) -> bool {
+    (min..=max).contains(&s.len()) && s.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+fn is_variant(s: &str) -> bool {
+    is_alnum(s, 5, 8) || (s.len() == 4 && s.starts_with(|c: char| c.is_ascii_digit()) && is_alnum(s, 4, 4))
+}
+
+fn is_region(s: &str) -> bool {
+    is_alpha(s, 2, 2) || (s.len() == 3 && s.chars().all(|c| c.is_ascii_digit()))
+}
+
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.trim().replace('_', "-");
+    let mut subtags = tag.split('-').peekable();
+    let mut normalized = Vec::new();
+
+    let language = subtags.next()?;
+    if language.eq_ignore_ascii_case("x") {
+        normalized.push("x".to_owned());
+    } else {
+        if !(is_alpha(language, 2, 3) || is_alpha(language, 5, 8)) {
+            return None;
+        }
+        normalized.push(language.to_ascii_lowercase());
+        let mut extlangs = 0;
+        while extlangs < 3 && language.len() <= 3 && subtags.peek().is_some_and(|s| is_alpha(s, 3, 3)) {
+            normalized.push(subtags.next()?.to_ascii_lowercase());
+            extlangs += 1;
+        }
+        if let Some(script) = subtags.next_if(|s| is_alpha(s, 4, 4)) {
+            let (first, rest) = script.split_at(1);
+            normalized.push(first.to_ascii_uppercase() + &rest.to_ascii_lowercase());
+        }
+        if let Some(region) = subtags.next_if(|s| is_region(s)) {
+            normalized.push(region.to_ascii_uppercase());
+        }
+        let mut variants = Vec::new();
+        while let Some(variant) = subtags.next_if(|s| is_variant(s)) {
+            let variant = variant.to_ascii_lowercase();
+            if variants.contains(&variant) {
+                return None;
+            }
+            variants.push(variant);
+        }
+        normalized.extend(variants);
+        while let Some(singleton) = subtags.next_if(|s| is_alnum(s, 1, 1) && !s.eq_ignore_ascii_case("x")) {
+            normalized.push(singleton.to_ascii_lowercase());
+            let mut extension = 0;
+            while let Some(s) = subtags.next_if(|s| is_alnum(s, 2, 8)) {
+                normalized.push(s.to_ascii_lowercase());
+                extension += 1;
+            }
+            if extension == 0 {
+                return None;
+            }
+        }
+        if subtags.next_if(|s| s.eq_ignore_ascii_case("x")).is_some() {
+            normalized.push("x".to_owned());
+        } else if subtags.peek().is_some() {
+            return None;
+        } else {
+            return Some(normalized.join("-"));
+        }
+    }
+
+    let mut private = 0;
+    for s in subtags {
+        if !is_alnum(s, 1, 8) {
+            return None;
+        }
+        normalized.push(s.to_ascii_lowercase());
+        private += 1;
+    }
+    (private > 0).then(|| normalized.join("-"))
+}
+
+pub fn english_name(tag: &str) -> Option<&'static str> {
+    COMMON_LANGUAGES
+        .iter()
+        .find(|(t, _)| t.eq_ignore_ascii_case(tag))
+        .map(|(_, name)| *name)
+}
+
+pub fn detect_tag(text: &str) -> Option<&'static str> {
+    let info = whatlang::detect(text).filter(whatlang::Info::is_reliable)?;
+    let code = info.lang().code();
+    Some(match code {
+        "afr" => "af",
+        "aka" => "ak",
+        "amh" => "am",
+        "ara" => "ar",
+        "aze" => "az",
+        "bel" => "be",
+        "ben" => "bn",
+        "bul" => "bg",
+        "cat" => "ca",
+        "ces" => "cs",
+        "cmn" => "zh",
+        "dan" => "da",
+        "deu" => "de",
+        "ell" => "el",
+        "eng" => "en",
+        "epo" => "eo",
+        "est" => "et",
+        "fin" => "fi",
+        "fra" => "fr",
+        "guj" => "gu",
+        "heb" => "he",
+        "hin" => "hi",
+        "hrv" => "hr",
+        "hun" => "hu",
+        "hye" => "hy",
+        "ind" => "id",
+        "ita" => "it",
+        "jav" => "jv",
+        "jpn" => "ja",
+        "kan" => "kn",
+        "kat" => "ka",
+        "khm" => "km",
+        "kor" => "ko",
+        "lat" => "la",
+        "lav" => "lv",
+        "lit" => "lt",
+        "mal" => "ml",
+        "mar" => "mr",
+        "mkd" => "mk",
+        "mya" => "my",
+        "nep" => "ne",
+        "nld" => "nl",
+        "nob" => "nb",
+        "ori" => "or",
+        "pan" => "pa",
+        "pes" => "fa",
+        "pol" => "pl",
+        "por" => "pt",
+        "ron" => "ro",
+        "rus" => "ru",
+        "sin" => "si",
+        "slk" => "sk",
+        "slv" => "sl",
+        "sna" => "sn",
+        "spa" => "es",
+        "srp" => "sr",
+        "swe" => "sv",
+        "tam" => "ta",
+        "tel" => "te",
+        "tgl" => "tl",
+        "tha" => "th",
+        "tuk" => "tk",
+        "tur" => "tr",
+        "ukr" => "uk",
+        "urd" => "ur",
+        "uzb" => "uz",
+        "vie" => "vi",
+        "yid" => "yi",
+        "zul" => "zu",
+        _ => code,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_case_and_separators() {
+        assert_eq!(normalize_tag("EN_us").as_deref(), Some("en-US"));
+        assert_eq!(normalize_tag("zh-hant-tw").as_deref(), Some("zh-Hant-TW"));
+        assert_eq!(normalize_tag(" es-419 ").as_deref(), Some("es-419"));
+        assert_eq!(normalize_tag("zh-YUE").as_deref(), Some("zh-yue"));
+    }
+
+    #[test]
+    fn accepts_variants_extensions_and_private_use() {
+        assert_eq!(normalize_tag("de-CH-1996").as_deref(), Some("de-CH-1996"));
+        assert_eq!(normalize_tag("en-u-CA-gregory").as_deref(), Some("en-u-ca-gregory"));
+        assert_eq!(normalize_tag("en-x-Klingon").as_deref(), Some("en-x-klingon"));
+        assert_eq!(normalize_tag("X-private").as_deref(), Some("x-private"));
+    }
+
+    #[test]
+    fn rejects_invalid_tags() {
+        assert_eq!(normalize_tag(""), None);
+        assert_eq!(normalize_tag("e"), None);
+        assert_eq!(normalize_tag("en-US-US"), None);
+        assert_eq!(normalize_tag("de-1996-1996"), None);
+        assert_eq!(normalize_tag("en-u"), None);
+        assert_eq!(normalize_tag("x"), None);
+        assert_eq!(normalize_tag("en-x"), None);
+    }
+
+    #[test]
+    fn detects_reliable_languages() {
+        let english = "We study how the distribution of prime numbers relates to the zeros of the zeta function \
+            and give a new bound on the error term of the prime number theorem.";
+        assert_eq!(detect_tag(english), Some("en"));
+        let russian = "Мы изучаем, как распределение простых чисел связано с нулями дзета-функции, \
+            и получаем новую оценку остаточного члена в теореме о распределении простых чисел.";
+        assert_eq!(detect_tag(russian), Some("ru"));
+        let japanese = "本論文では素数の分布とゼータ関数の零点の関係を調べ、素数定理の誤差項に新しい評価を与える。";
+        assert_eq!(detect_tag(japanese), Some("ja"));
+    }
+
+    #[test]
+    fn short_text_is_not_detected() {
+        assert_eq!(detect_tag(""), None);
+        assert_eq!(detect_tag("Primes"), None);
+    }
+}
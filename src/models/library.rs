@@ -0,0 +1,195 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+};
+
+use bson::oid::ObjectId;
+use gloo::storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+
+use super::common::{AppConfig, AppResult, FetchOther, FetchRes};
+
+#[derive(PartialEq)]
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+#[derive(Clone, Default)]
+pub struct Library {
+    pub bookmarks: Rc<BTreeSet<ObjectId>>,
+    pub lists: Rc<BTreeMap<Rc<str>, BTreeSet<ObjectId>>>,
+}
+
+#[derive(PartialEq)]
+#[derive(Clone, Copy, Default)]
+pub enum LibraryStore {
+    Account,
+    #[default]
+    Local,
+}
+
+pub struct LoadedLibrary {
+    pub store: LibraryStore,
+    pub val: Library,
+    pub diverged: Option<Library>,
+}
+
+impl Library {
+    const STORAGE_KEY: &'static str = "library";
+
+    pub fn is_empty(&self) -> bool {
+        self.bookmarks.is_empty() && self.lists.is_empty()
+    }
+
+    pub fn merge(&self, other: &Self) -> Self {
+        let mut merged = self.clone();
+        Rc::make_mut(&mut merged.bookmarks).extend(other.bookmarks.iter().copied());
+        let lists = Rc::make_mut(&mut merged.lists);
+        for (name, ids) in other.lists.iter() {
+            lists.entry(name.clone()).or_default().extend(ids.iter().copied());
+        }
+        merged
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let lists = self
+            .lists
+            .iter()
+            .filter_map(|(name, ids)| match other.lists.get(name) {
+                Some(others) => {
+                    let ids = ids.difference(others).copied().collect::<BTreeSet<_>>();
+                    (!ids.is_empty()).then(|| (name.clone(), ids))
+                }
+                None => Some((name.clone(), ids.clone())),
+            })
+            .collect();
+        Self {
+            bookmarks: Rc::new(self.bookmarks.difference(&other.bookmarks).copied().collect()),
+            lists: Rc::new(lists),
+        }
+    }
+
+    pub fn load_local() -> Self {
+        LocalStorage::get(Self::STORAGE_KEY).unwrap_or_default()
+    }
+
+    pub fn store_local(&self) {
+        if let Err(e) = LocalStorage::set(Self::STORAGE_KEY, self) {
+            gloo::console::error!(e.to_string());
+        }
+    }
+
+    pub fn clear_local() {
+        LocalStorage::delete(Self::STORAGE_KEY);
+    }
+
+    pub async fn try_get(cfg: &AppConfig) -> AppResult<FetchRes<Self>> {
+        <FetchRes<Self>>::try_from_gloo_res(
+            gloo::net::http::Request::get(cfg.api.join("library")?.as_str())
+                .credentials(web_sys::RequestCredentials::Include)
+                .send()
+                .await,
+        )
+        .await
+    }
+
+    pub async fn try_put(&self, cfg: &AppConfig) -> AppResult<FetchRes<Self>> {
+        <FetchRes<Self>>::try_from_gloo_res(
+            gloo::net::http::Request::put(cfg.api.join("library")?.as_str())
+                .credentials(web_sys::RequestCredentials::Include)
+                .json(self)?
+                .send()
+                .await,
+        )
+        .await
+    }
+
+    pub async fn load(cfg: &AppConfig) -> AppResult<Result<LoadedLibrary, FetchOther>> {
+        let local = Self::load_local();
+        let remote = match Self::try_get(cfg).await? {
+            FetchRes::Body(remote) => remote,
+            FetchRes::Other(other) if matches!(other.status, 401 | 403) => {
+                return Ok(Ok(LoadedLibrary {
+                    store: LibraryStore::Local,
+                    val: local,
+                    diverged: None,
+                }))
+            }
+            FetchRes::Other(other) => return Ok(Err(other)),
+        };
+        if local.is_empty() || remote.merge(&local) == remote {
+            Self::clear_local();
+            return Ok(Ok(LoadedLibrary {
+                store: LibraryStore::Account,
+                val: remote,
+                diverged: None,
+            }));
+        }
+        if remote.is_empty() {
+            if let FetchRes::Body(uploaded) = local.try_put(cfg).await? {
+                Self::clear_local();
+                return Ok(Ok(LoadedLibrary {
+                    store: LibraryStore::Account,
+                    val: uploaded,
+                    diverged: None,
+                }));
+            }
+        }
+        Ok(Ok(LoadedLibrary {
+            store: LibraryStore::Account,
+            val: remote,
+            diverged: Some(local),
+        }))
+    }
+
+    pub async fn save(&self, cfg: &AppConfig, store: LibraryStore) -> AppResult<FetchRes<Self>> {
+        match store {
+            LibraryStore::Account => self.try_put(cfg).await,
+            LibraryStore::Local => {
+                self.store_local();
+                Ok(FetchRes::Body(self.clone()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn library(bookmarks: &[ObjectId], lists: &[(&str, &[ObjectId])]) -> Library {
+        Library {
+            bookmarks: Rc::new(bookmarks.iter().copied().collect()),
+            lists: Rc::new(lists.iter().map(|(name, ids)| (Rc::from(*name), ids.iter().copied().collect())).collect()),
+        }
+    }
+
+    #[test]
+    fn merge_unions_bookmarks_and_lists() {
+        let [a, b, c] = [ObjectId::new(), ObjectId::new(), ObjectId::new()];
+        let local = library(&[a, b], &[("reading", &[a]), ("later", &[b])]);
+        let remote = library(&[b, c], &[("reading", &[c]), ("done", &[a])]);
+        let merged = local.merge(&remote);
+        assert!(merged == library(&[a, b, c], &[("reading", &[a, c]), ("later", &[b]), ("done", &[a])]));
+        assert!(merged == remote.merge(&local));
+    }
+
+    #[test]
+    fn difference_keeps_only_one_sided_entries() {
+        let [a, b, c] = [ObjectId::new(), ObjectId::new(), ObjectId::new()];
+        let account = library(&[a, b], &[("reading", &[a, b]), ("done", &[c])]);
+        let local = library(&[b, c], &[("reading", &[b]), ("later", &[])]);
+        assert!(account.difference(&local) == library(&[a], &[("reading", &[a]), ("done", &[c])]));
+        assert!(local.difference(&account) == library(&[c], &[("later", &[])]));
+        assert!(account.difference(&account).is_empty());
+    }
+
+    #[test]
+    fn merge_leaves_operands_untouched() {
+        let [a, b] = [ObjectId::new(), ObjectId::new()];
+        let local = library(&[a], &[("reading", &[a])]);
+        let remote = library(&[b], &[("reading", &[b])]);
+        let merged = local.merge(&remote);
+        assert!(local == library(&[a], &[("reading", &[a])]));
+        assert!(merged.merge(&Library::default()) == merged);
+        assert!(Library::default().merge(&Library::default()).is_empty());
+    }
+}
@@ -0,0 +1,412 @@
+use std::{iter::Peekable, str::Chars};
+
+const IDENTIFIERS: [(&str, &str); 40] = [
+    ("alpha", "α"),
+    ("beta", "β"),
+    ("gamma", "γ"),
+    ("delta", "δ"),
+    ("epsilon", "ϵ"),
+    ("varepsilon", "ε"),
+    ("zeta", "ζ"),
+    ("eta", "η"),
+    ("theta", "θ"),
+    ("vartheta", "ϑ"),
+    ("iota", "ι"),
+    ("kappa", "κ"),
+    ("lambda", "λ"),
+    ("mu", "μ"),
+    ("nu", "ν"),
+    ("xi", "ξ"),
+    ("pi", "π"),
+    ("rho", "ρ"),
+    ("sigma", "σ"),
+    ("tau", "τ"),
+    ("upsilon", "υ"),
+    ("phi", "ϕ"),
+    ("varphi", "φ"),
+    ("chi", "χ"),
+    ("psi", "ψ"),
+    ("omega", "ω"),
+    ("Gamma", "Γ"),
+    ("Delta", "Δ"),
+    ("Theta", "Θ"),
+    ("Lambda", "Λ"),
+    ("Xi", "Ξ"),
+    ("Pi", "Π"),
+    ("Sigma", "Σ"),
+    ("Upsilon", "Υ"),
+    ("Phi", "Φ"),
+    ("Psi", "Ψ"),
+    ("Omega", "Ω"),
+    ("infty", "∞"),
+    ("ell", "ℓ"),
+    ("emptyset", "∅"),
+];
+
+const OPERATORS: [(&str, &str); 42] = [
+    ("pm", "±"),
+    ("mp", "∓"),
+    ("times", "×"),
+    ("cdot", "⋅"),
+    ("div", "÷"),
+    ("ast", "∗"),
+    ("circ", "∘"),
+    ("leq", "≤"),
+    ("le", "≤"),
+    ("geq", "≥"),
+    ("ge", "≥"),
+    ("neq", "≠"),
+    ("ne", "≠"),
+    ("approx", "≈"),
+    ("equiv", "≡"),
+    ("sim", "∼"),
+    ("propto", "∝"),
+    ("to", "→"),
+    ("rightarrow", "→"),
+    ("leftarrow", "←"),
+    ("Rightarrow", "⇒"),
+    ("Leftarrow", "⇐"),
+    ("iff", "⇔"),
+    ("mapsto", "↦"),
+    ("in", "∈"),
+    ("notin", "∉"),
+    ("subset", "⊂"),
+    ("subseteq", "⊆"),
+    ("supset", "⊃"),
+    ("cup", "∪"),
+    ("cap", "∩"),
+    ("setminus", "∖"),
+    ("forall", "∀"),
+    ("exists", "∃"),
+    ("neg", "¬"),
+    ("partial", "∂"),
+    ("nabla", "∇"),
+    ("ldots", "…"),
+    ("cdots", "⋯"),
+    ("sum", "∑"),
+    ("prod", "∏"),
+    ("int", "∫"),
+];
+
+const FUNCTIONS: [&str; 18] = [
+    "sin", "cos", "tan", "cot", "sec", "csc", "log", "ln", "exp", "lim", "max", "min", "sup", "inf",
+    "det", "arg", "dim", "gcd",
+];
+
+const SPACES: [(&str, &str); 6] = [
+    (",", "0.1667em"),
+    (":", "0.2222em"),
+    (";", "0.2778em"),
+    (" ", "0.2778em"),
+    ("quad", "1em"),
+    ("qquad", "2em"),
+];
+
+const VARIANTS: [(&str, &str); 6] = [
+    ("mathrm", "normal"),
+    ("mathbf", "bold"),
+    ("mathit", "italic"),
+    ("mathbb", "double-struck"),
+    ("mathcal", "script"),
+    ("mathsf", "sans-serif"),
+];
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn lookup(table: &[(&str, &'static str)], name: &str) -> Option<&'static str> {
+    table.iter().find(|(k, _)| *k == name).map(|(_, v)| *v)
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn command(&mut self) -> String {
+        let mut name = String::new();
+        while let Some(c) = self.chars.next_if(|c| c.is_ascii_alphabetic()) {
+            name.push(c);
+        }
+        if name.is_empty() {
+            if let Some(c) = self.chars.next() {
+                name.push(c);
+            }
+        }
+        name
+    }
+
+    fn raw_group(&mut self) -> String {
+        self.skip_whitespace();
+        let mut raw = String::new();
+        if self.chars.next_if_eq(&'{').is_none() {
+            return self.chars.next().map(String::from).unwrap_or_default();
+        }
+        let mut depth = 0;
+        for c in self.chars.by_ref() {
+            match c {
+                '{' => depth += 1,
+                '}' if depth == 0 => break,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            raw.push(c);
+        }
+        raw
+    }
+
+    fn optional(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        self.chars.next_if_eq(&'[')?;
+        let mut raw = String::new();
+        for c in self.chars.by_ref() {
+            if c == ']' {
+                break;
+            }
+            raw.push(c);
+        }
+        Some(raw)
+    }
+
+    fn delimiter(&mut self) -> String {
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some('.') | None => String::new(),
+            Some('\\') => {
+                let name = self.command();
+                let delim = match name.as_str() {
+                    "{" | "}" | "|" => name.as_str(),
+                    "langle" => "⟨",
+                    "rangle" => "⟩",
+                    _ => return format!("<merror><mtext>\\{}</mtext></merror>", escape(&name)),
+                };
+                format!("<mo>{}</mo>", escape(if delim == "|" { "‖" } else { delim }))
+            }
+            Some(c) => format!("<mo>{}</mo>", escape(&c.to_string())),
+        }
+    }
+
+    fn row(&mut self) -> String {
+        let mut nodes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.chars.peek() {
+                None | Some('}') => break,
+                Some('\\') => {
+                    let mut lookahead = self.chars.clone();
+                    lookahead.next();
+                    if lookahead.by_ref().take(5).collect::<String>() == "right"
+                        && !lookahead.next().is_some_and(|c| c.is_ascii_alphabetic())
+                    {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+            nodes.push(self.scripted());
+        }
+        match nodes.len() {
+            1 => nodes.remove(0),
+            _ => format!("<mrow>{}</mrow>", nodes.concat()),
+        }
+    }
+
+    fn scripted(&mut self) -> String {
+        let base = self.atom();
+        let mut sub = None;
+        let mut sup = None;
+        loop {
+            self.skip_whitespace();
+            match self.chars.peek() {
+                Some('_') if sub.is_none() => {
+                    self.chars.next();
+                    sub = Some(self.atom());
+                }
+                Some('^') if sup.is_none() => {
+                    self.chars.next();
+                    sup = Some(self.atom());
+                }
+                _ => break,
+            }
+        }
+        match (sub, sup) {
+            (Some(sub), Some(sup)) => format!("<msubsup>{base}{sub}{sup}</msubsup>"),
+            (Some(sub), None) => format!("<msub>{base}{sub}</msub>"),
+            (None, Some(sup)) => format!("<msup>{base}{sup}</msup>"),
+            (None, None) => base,
+        }
+    }
+
+    fn atom(&mut self) -> String {
+        self.skip_whitespace();
+        let c = match self.chars.next() {
+            Some(c) => c,
+            None => return "<mrow></mrow>".to_owned(),
+        };
+        match c {
+            '{' => {
+                let row = self.row();
+                self.chars.next_if_eq(&'}');
+                match row.starts_with("<mrow>") {
+                    true => row,
+                    false => format!("<mrow>{row}</mrow>"),
+                }
+            }
+            '\\' => self.control(),
+            '0'..='9' | '.' => {
+                let mut num = c.to_string();
+                while let Some(c) = self.chars.next_if(|c| c.is_ascii_digit() || *c == '.') {
+                    num.push(c);
+                }
+                format!("<mn>{}</mn>", escape(&num))
+            }
+            c if c.is_alphabetic() => format!("<mi>{}</mi>", escape(&c.to_string())),
+            '\'' => "<mo>′</mo>".to_owned(),
+            '~' => "<mspace width=\"0.2778em\"></mspace>".to_owned(),
+            c => format!("<mo>{}</mo>", escape(&c.to_string())),
+        }
+    }
+
+    fn control(&mut self) -> String {
+        let name = self.command();
+        if let Some(identifier) = lookup(&IDENTIFIERS, &name) {
+            let variant = if name.starts_with(char::is_uppercase) { " mathvariant=\"normal\"" } else { "" };
+            return format!("<mi{variant}>{identifier}</mi>");
+        }
+        if let Some(operator) = lookup(&OPERATORS, &name) {
+            return format!("<mo>{operator}</mo>");
+        }
+        if FUNCTIONS.contains(&name.as_str()) {
+            return format!("<mi>{name}</mi>");
+        }
+        if let Some(width) = lookup(&SPACES, &name) {
+            return format!("<mspace width=\"{width}\"></mspace>");
+        }
+        if let Some(variant) = lookup(&VARIANTS, &name) {
+            return format!("<mi mathvariant=\"{}\">{}</mi>", variant, escape(self.raw_group().trim()));
+        }
+        match name.as_str() {
+            "frac" | "dfrac" | "tfrac" => {
+                let num = self.atom();
+                let den = self.atom();
+                format!("<mfrac>{num}{den}</mfrac>")
+            }
+            "sqrt" => match self.optional() {
+                Some(index) => {
+                    let index = Parser { chars: index.chars().peekable() }.row();
+                    format!("<mroot>{}{}</mroot>", self.atom(), index)
+                }
+                None => format!("<msqrt>{}</msqrt>", self.atom()),
+            },
+            "text" | "mbox" => format!("<mtext>{}</mtext>", escape(&self.raw_group())),
+            "operatorname" => format!("<mi>{}</mi>", escape(self.raw_group().trim())),
+            "left" => {
+                let open = self.delimiter();
+                let row = self.row();
+                let close = match self.chars.peek() {
+                    Some('\\') => {
+                        self.chars.next();
+                        self.command();
+                        self.delimiter()
+                    }
+                    _ => String::new(),
+                };
+                format!("<mrow>{open}{row}{close}</mrow>")
+            }
+            "!" => String::new(),
+            "{" | "}" | "%" | "$" | "#" | "_" | "&" | "|" => format!("<mo>{}</mo>", escape(&name)),
+            "\\" => "<mspace linebreak=\"newline\"></mspace>".to_owned(),
+            _ => format!("<merror><mtext>\\{}</mtext></merror>", escape(&name)),
+        }
+    }
+}
+
+pub fn to_mathml(latex: &str, display: bool) -> String {
+    let mut parser = Parser { chars: latex.chars().peekable() };
+    let mut rows = Vec::new();
+    loop {
+        rows.push(parser.row());
+        match parser.chars.next() {
+            Some('}') => rows.push("<merror><mtext>}</mtext></merror>".to_owned()),
+            Some(_) => {
+                parser.command();
+                parser.delimiter();
+            }
+            None => break,
+        }
+    }
+    format!(
+        "<math display=\"{}\"><semantics><mrow>{}</mrow><annotation encoding=\"application/x-tex\">{}</annotation></semantics></math>",
+        if display { "block" } else { "inline" },
+        rows.concat(),
+        escape(latex),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body(latex: &str) -> String {
+        let mathml = to_mathml(latex, false);
+        let start = mathml.find("<semantics><mrow>").unwrap() + 17;
+        let end = mathml.rfind("</mrow><annotation").unwrap();
+        mathml[start..end].to_owned()
+    }
+
+    #[test]
+    fn wraps_in_math_with_annotation() {
+        assert_eq!(
+            to_mathml("a<b", true),
+            "<math display=\"block\"><semantics><mrow><mrow><mi>a</mi><mo>&lt;</mo><mi>b</mi></mrow></mrow>\
+             <annotation encoding=\"application/x-tex\">a&lt;b</annotation></semantics></math>"
+        );
+        assert!(to_mathml("x", false).starts_with("<math display=\"inline\">"));
+    }
+
+    #[test]
+    fn scripts_and_fractions() {
+        assert_eq!(body("x_i^2"), "<msubsup><mi>x</mi><mi>i</mi><mn>2</mn></msubsup>");
+        assert_eq!(body("e^{i\\pi}"), "<msup><mi>e</mi><mrow><mi>i</mi><mi>π</mi></mrow></msup>");
+        assert_eq!(body("\\frac{1}{n}"), "<mfrac><mrow><mn>1</mn></mrow><mrow><mi>n</mi></mrow></mfrac>");
+        assert_eq!(body("\\sqrt[3]{x}"), "<mroot><mrow><mi>x</mi></mrow><mn>3</mn></mroot>");
+    }
+
+    #[test]
+    fn commands_and_delimiters() {
+        assert_eq!(body("\\Gamma"), "<mi mathvariant=\"normal\">Γ</mi>");
+        assert_eq!(body("\\mathbb{R}"), "<mi mathvariant=\"double-struck\">R</mi>");
+        assert_eq!(body("\\text{if }"), "<mtext>if </mtext>");
+        assert_eq!(body("\\left(x\\right)"), "<mrow><mo>(</mo><mi>x</mi><mo>)</mo></mrow>");
+    }
+
+    #[test]
+    fn right_arrows_do_not_close_rows() {
+        assert_eq!(body("x \\rightarrow y"), "<mrow><mi>x</mi><mo>→</mo><mi>y</mi></mrow>");
+        assert_eq!(
+            body("\\left( a \\rightarrow b \\right)"),
+            "<mrow><mo>(</mo><mrow><mi>a</mi><mo>→</mo><mi>b</mi></mrow><mo>)</mo></mrow>"
+        );
+    }
+
+    #[test]
+    fn unknown_commands_and_stray_braces_are_errors() {
+        assert_eq!(body("\\foo"), "<merror><mtext>\\foo</mtext></merror>");
+        assert_eq!(body("x}"), "<mi>x</mi><merror><mtext>}</mtext></merror><mrow></mrow>");
+    }
+}
@@ -2,4 +2,15 @@ pub mod review;
 pub mod profile;
 pub mod common;
 pub mod thesis;
-pub mod version;
\ No newline at end of file
+pub mod version;
+pub mod search;
+pub mod library;
+pub mod citation;
+pub mod doi;
+pub mod language;
+pub mod math;
+pub mod upload;
+pub mod validation;
+pub mod archive;
+pub mod digest;
+pub mod transfer;
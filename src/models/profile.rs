@@ -55,4 +55,16 @@ impl Default for Setting {
 pub struct Profile {
     pub public_profile: PublicProfile,
     pub setting: Setting,
+}
+
+impl Profile {
+    pub async fn try_get_self(cfg: &AppConfig) -> Result<FetchRes<Rc<Self>>, AppError> {
+        <FetchRes<Rc<Self>>>::try_from_gloo_res(
+            gloo::net::http::Request::get(cfg.api.join("profile")?.as_str())
+                .credentials(web_sys::RequestCredentials::Include)
+                .send()
+                .await,
+        )
+        .await
+    }
 }
\ No newline at end of file
@@ -0,0 +1,108 @@
+use std::rc::Rc;
+
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use gloo::storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+
+use super::common::{AppConfig, AppResult, FetchOther, FetchRes};
+
+#[derive(PartialEq)]
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+#[derive(Clone, Default)]
+pub struct ThesisQuery {
+    pub q: Rc<str>,
+    pub keyword: Rc<str>,
+    pub language: Rc<str>,
+    pub is_passed: bool,
+}
+
+impl ThesisQuery {
+    pub fn pairs(&self) -> Vec<(&'static str, &str)> {
+        let mut pairs = [("q", &*self.q), ("keyword", &*self.keyword), ("language", &*self.language)]
+            .into_iter()
+            .filter(|(_, v)| !v.is_empty())
+            .collect::<Vec<_>>();
+        if self.is_passed {
+            pairs.push(("is_passed", "true"));
+        }
+        pairs
+    }
+}
+
+#[derive(PartialEq)]
+#[derive(Serialize, Deserialize)]
+#[derive(Clone)]
+pub struct SavedSearch {
+    pub _id: ObjectId,
+    pub name: Rc<str>,
+    pub query: ThesisQuery,
+    pub visited_at: DateTime<Utc>,
+}
+
+#[derive(PartialEq)]
+#[derive(Clone, Copy, Default)]
+pub enum SearchStore {
+    Server,
+    #[default]
+    Local,
+}
+
+impl SavedSearch {
+    const STORAGE_KEY: &'static str = "saved_searches";
+
+    pub fn new(name: Rc<str>, query: ThesisQuery) -> Self {
+        Self {
+            _id: ObjectId::new(),
+            name,
+            query,
+            visited_at: Utc::now(),
+        }
+    }
+
+    pub async fn try_list(cfg: &AppConfig) -> AppResult<FetchRes<Rc<Vec<Rc<Self>>>>> {
+        <FetchRes<Rc<Vec<Rc<Self>>>>>::try_from_gloo_res(
+            gloo::net::http::Request::get(cfg.api.join("searches")?.as_str())
+                .credentials(web_sys::RequestCredentials::Include)
+                .send()
+                .await,
+        )
+        .await
+    }
+
+    pub async fn try_put(&self, cfg: &AppConfig) -> AppResult<FetchRes<Rc<Self>>> {
+        <FetchRes<Rc<Self>>>::try_from_gloo_res(
+            gloo::net::http::Request::put(
+                cfg.api.join(&format!("searches/{}", self._id.to_hex()))?.as_str(),
+            )
+            .credentials(web_sys::RequestCredentials::Include)
+            .json(self)?
+            .send()
+            .await,
+        )
+        .await
+    }
+
+    pub async fn try_delete(cfg: &AppConfig, id: ObjectId) -> AppResult<Option<FetchOther>> {
+        FetchOther::try_from_empty_res(
+            gloo::net::http::Request::delete(
+                cfg.api.join(&format!("searches/{}", id.to_hex()))?.as_str(),
+            )
+            .credentials(web_sys::RequestCredentials::Include)
+            .send()
+            .await,
+        )
+        .await
+    }
+
+    pub fn load_local() -> Vec<Rc<Self>> {
+        LocalStorage::get(Self::STORAGE_KEY).unwrap_or_default()
+    }
+
+    pub fn store_local(vals: &[Rc<Self>]) {
+        if let Err(e) = LocalStorage::set(Self::STORAGE_KEY, vals) {
+            gloo::console::error!(e.to_string());
+        }
+    }
+}
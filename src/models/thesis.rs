@@ -1,9 +1,12 @@
-use std::{rc::Rc, collections::BTreeSet};
+use std::{rc::Rc, collections::{BTreeMap, BTreeSet}};
 
 use bson::oid::ObjectId;
 use serde::{Serialize, Deserialize};
 
-use super::common::{AppConfig, AppResult, FetchRes};
+use super::{
+    common::{AppConfig, AppResult, FetchRes},
+    doi::DoiRequest,
+};
 
 #[derive(PartialEq)]
 #[derive(Serialize, Deserialize)]
@@ -18,6 +21,25 @@ pub struct ThesisId {
 
 #[derive(PartialEq)]
 #[derive(Serialize, Deserialize)]
+#[derive(Clone, Default)]
+pub struct Translation {
+    pub title: Rc<str>,
+    pub abstraction: Rc<str>,
+}
+
+#[derive(PartialEq)]
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+#[derive(Default)]
+pub struct Withdrawal {
+    pub reason: Rc<str>,
+    pub withdrawn_at: Rc<chrono::DateTime<chrono::Utc>>,
+    pub withdrawn_by: Option<ObjectId>,
+}
+
+#[derive(PartialEq)]
+#[derive(Serialize, Deserialize)]
+#[derive(Default)]
 pub struct Thesis {
     #[serde(flatten)]
     pub id: ThesisId,
@@ -27,20 +49,43 @@ pub struct Thesis {
     // pub magazine_id: ObjectId,
     #[serde(default)]
     pub doi: Option<Rc<str>>,
+    #[serde(default)]
+    pub doi_request: Option<DoiRequest>,
     pub title: Rc<str>,
     pub abstraction: Rc<str>,
     #[serde(default)]
     pub keywords: Rc<Vec<Rc<str>>>,
     #[serde(default)]
     pub languages: Rc<BTreeSet<Rc<str>>>,
+    #[serde(default)]
+    pub translations: Rc<BTreeMap<Rc<str>, Translation>>,
+    #[serde(default)]
+    pub withdrawal: Option<Withdrawal>,
+    #[serde(default, skip_serializing)]
+    pub is_editor: bool,
+    #[serde(skip)]
+    pub etag: Option<Rc<str>>,
 }
 
 impl Thesis {
+    async fn try_from_tagged_res(
+        res: Result<gloo::net::http::Response, gloo::net::Error>,
+    ) -> AppResult<FetchRes<Rc<Self>>> {
+        let etag = res.as_ref().ok().and_then(|res| res.headers().get("etag"));
+        Ok(match <FetchRes<Self>>::try_from_gloo_res(res).await? {
+            FetchRes::Body(val) => FetchRes::Body(Rc::new(Self {
+                etag: etag.map(Rc::from),
+                ..val
+            })),
+            FetchRes::Other(other) => FetchRes::Other(other),
+        })
+    }
+
     pub async fn try_get(
         cfg: &AppConfig,
         id: ObjectId,
     ) -> AppResult<FetchRes<Rc<Self>>> {
-        <FetchRes<Rc<Self>>>::try_from_gloo_res(
+        Self::try_from_tagged_res(
             gloo::net::http::Request::get(
                 cfg.api.join(&format!("theses/{}", id.to_hex()))?.as_str(),
             )
@@ -50,4 +95,80 @@ impl Thesis {
         )
         .await
     }
+
+    pub async fn try_patch(
+        cfg: &AppConfig,
+        id: ObjectId,
+        etag: Option<&str>,
+        changes: &serde_json::Map<String, serde_json::Value>,
+    ) -> AppResult<FetchRes<Rc<Self>>> {
+        let req = gloo::net::http::Request::patch(
+            cfg.api.join(&format!("theses/{}", id.to_hex()))?.as_str(),
+        )
+        .credentials(web_sys::RequestCredentials::Include);
+        let req = match etag {
+            Some(etag) => req.header("If-Match", etag),
+            None => req,
+        };
+        Self::try_from_tagged_res(req.json(changes)?.send().await).await
+    }
+
+    pub async fn try_request_doi(
+        cfg: &AppConfig,
+        id: ObjectId,
+    ) -> AppResult<FetchRes<Rc<Self>>> {
+        Self::try_from_tagged_res(
+            gloo::net::http::Request::post(
+                cfg.api.join(&format!("theses/{}/doi", id.to_hex()))?.as_str(),
+            )
+            .credentials(web_sys::RequestCredentials::Include)
+            .send()
+            .await,
+        )
+        .await
+    }
+
+    pub async fn try_withdraw(
+        cfg: &AppConfig,
+        id: ObjectId,
+        reason: &str,
+    ) -> AppResult<FetchRes<Rc<Self>>> {
+        Self::try_from_tagged_res(
+            gloo::net::http::Request::post(
+                cfg.api.join(&format!("theses/{}/withdraw", id.to_hex()))?.as_str(),
+            )
+            .credentials(web_sys::RequestCredentials::Include)
+            .json(&serde_json::json!({ "reason": reason }))?
+            .send()
+            .await,
+        )
+        .await
+    }
+
+    pub async fn try_list(
+        cfg: &AppConfig,
+        query: &[(&str, &str)],
+    ) -> AppResult<FetchRes<Rc<Vec<Rc<Self>>>>> {
+        let mut url = cfg.api.join("theses")?;
+        url.query_pairs_mut().extend_pairs(query);
+        <FetchRes<Rc<Vec<Rc<Self>>>>>::try_from_gloo_res(
+            gloo::net::http::Request::get(url.as_str())
+                .credentials(web_sys::RequestCredentials::Include)
+                .send()
+                .await,
+        )
+        .await
+    }
+
+    pub fn title_in(&self, language: Option<&str>) -> Rc<str> {
+        language
+            .and_then(|l| self.translations.get(l))
+            .map_or_else(|| self.title.clone(), |t| t.title.clone())
+    }
+
+    pub fn abstraction_in(&self, language: Option<&str>) -> Rc<str> {
+        language
+            .and_then(|l| self.translations.get(l))
+            .map_or_else(|| self.abstraction.clone(), |t| t.abstraction.clone())
+    }
 }
@@ -0,0 +1,71 @@
+use std::rc::Rc;
+
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::common::{AppConfig, AppResult, FetchOther, FetchRes};
+
+#[derive(PartialEq)]
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+#[derive(Default)]
+pub struct Transfer {
+    pub _id: ObjectId,
+    pub thesis_id: ObjectId,
+    pub from_id: ObjectId,
+    pub to_id: ObjectId,
+    pub requested_at: Rc<DateTime<Utc>>,
+}
+
+impl Transfer {
+    pub async fn try_create(cfg: &AppConfig, thesis_id: ObjectId, to_id: ObjectId) -> AppResult<FetchRes<Rc<Self>>> {
+        <FetchRes<Rc<Self>>>::try_from_gloo_res(
+            gloo::net::http::Request::post(cfg.api.join("transfers")?.as_str())
+                .credentials(web_sys::RequestCredentials::Include)
+                .json(&serde_json::json!({ "thesis_id": thesis_id, "to_id": to_id }))?
+                .send()
+                .await,
+        )
+        .await
+    }
+
+    pub async fn try_list(
+        cfg: &AppConfig,
+        query: &[(&str, &str)],
+    ) -> AppResult<FetchRes<Rc<Vec<Rc<Self>>>>> {
+        let mut url = cfg.api.join("transfers")?;
+        url.query_pairs_mut().extend_pairs(query);
+        <FetchRes<Rc<Vec<Rc<Self>>>>>::try_from_gloo_res(
+            gloo::net::http::Request::get(url.as_str())
+                .credentials(web_sys::RequestCredentials::Include)
+                .send()
+                .await,
+        )
+        .await
+    }
+
+    pub async fn try_accept(&self, cfg: &AppConfig) -> AppResult<Option<FetchOther>> {
+        FetchOther::try_from_empty_res(
+            gloo::net::http::Request::post(
+                cfg.api.join(&format!("transfers/{}/accept", self._id.to_hex()))?.as_str(),
+            )
+            .credentials(web_sys::RequestCredentials::Include)
+            .send()
+            .await,
+        )
+        .await
+    }
+
+    pub async fn try_delete(&self, cfg: &AppConfig) -> AppResult<Option<FetchOther>> {
+        FetchOther::try_from_empty_res(
+            gloo::net::http::Request::delete(
+                cfg.api.join(&format!("transfers/{}", self._id.to_hex()))?.as_str(),
+            )
+            .credentials(web_sys::RequestCredentials::Include)
+            .send()
+            .await,
+        )
+        .await
+    }
+}
@@ -0,0 +1,196 @@
+use std::{collections::BTreeMap, rc::Rc};
+
+use bson::oid::ObjectId;
+use gloo::{
+    file::File,
+    storage::{LocalStorage, Storage},
+};
+use serde::{Deserialize, Serialize};
+use web_sys::AbortSignal;
+
+use super::common::{AppConfig, AppResult, FetchOther, FetchRes};
+
+pub const CHUNK_SIZE: u64 = 1 << 20;
+
+#[derive(PartialEq)]
+#[derive(Serialize, Deserialize)]
+#[derive(Clone)]
+pub struct UploadSession {
+    pub _id: ObjectId,
+    pub name: Rc<str>,
+    pub size: u64,
+    pub last_modified: u64,
+    #[serde(default)]
+    pub sha256: Rc<str>,
+}
+
+fn last_modified(file: &File) -> u64 {
+    file.last_modified_time()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+impl UploadSession {
+    pub fn matches(&self, file: &File, sha256: &str) -> bool {
+        *self.name == file.name()
+            && self.size == file.size()
+            && self.last_modified == last_modified(file)
+            && *self.sha256 == *sha256
+    }
+
+    pub async fn try_create(cfg: &AppConfig, file: &File, sha256: Rc<str>) -> AppResult<FetchRes<Self>> {
+        let body = serde_json::json!({
+            "name": file.name(),
+            "size": file.size(),
+            "mime": file.raw_mime_type(),
+            "sha256": sha256,
+        });
+        let res = <FetchRes<ObjectId>>::try_from_gloo_res(
+            gloo::net::http::Request::post(cfg.api.join("uploads")?.as_str())
+                .credentials(web_sys::RequestCredentials::Include)
+                .json(&body)?
+                .send()
+                .await,
+        )
+        .await?;
+        Ok(match res {
+            FetchRes::Body(_id) => FetchRes::Body(Self {
+                _id,
+                name: file.name().into(),
+                size: file.size(),
+                last_modified: last_modified(file),
+                sha256,
+            }),
+            FetchRes::Other(other) => FetchRes::Other(other),
+        })
+    }
+
+    pub async fn try_offset(&self, cfg: &AppConfig) -> AppResult<FetchRes<u64>> {
+        <FetchRes<u64>>::try_from_gloo_res(
+            gloo::net::http::Request::get(
+                cfg.api.join(&format!("uploads/{}", self._id.to_hex()))?.as_str(),
+            )
+            .credentials(web_sys::RequestCredentials::Include)
+            .send()
+            .await,
+        )
+        .await
+    }
+
+    pub async fn try_put_chunk(
+        &self,
+        cfg: &AppConfig,
+        file: &File,
+        start: u64,
+        end: u64,
+        signal: &AbortSignal,
+    ) -> AppResult<Option<FetchOther>> {
+        FetchOther::try_from_empty_res(
+            gloo::net::http::Request::put(
+                cfg.api.join(&format!("uploads/{}", self._id.to_hex()))?.as_str(),
+            )
+            .credentials(web_sys::RequestCredentials::Include)
+            .header("Content-Range", &format!("bytes {}-{}/{}", start, end - 1, self.size))
+            .abort_signal(Some(signal))
+            .body(gloo::file::Blob::from(file.slice(start, end)))?
+            .send()
+            .await,
+        )
+        .await
+    }
+
+    pub async fn try_delete(&self, cfg: &AppConfig) -> AppResult<Option<FetchOther>> {
+        FetchOther::try_from_empty_res(
+            gloo::net::http::Request::delete(
+                cfg.api.join(&format!("uploads/{}", self._id.to_hex()))?.as_str(),
+            )
+            .credentials(web_sys::RequestCredentials::Include)
+            .send()
+            .await,
+        )
+        .await
+    }
+}
+
+#[derive(PartialEq)]
+#[derive(Serialize, Deserialize)]
+#[derive(Clone)]
+pub struct PendingCommit {
+    pub message: Rc<str>,
+    pub major: bool,
+    pub release: UploadSession,
+    pub source: Option<UploadSession>,
+    #[serde(default)]
+    pub supplements: Vec<PendingSupplement>,
+}
+
+#[derive(PartialEq)]
+#[derive(Serialize, Deserialize)]
+#[derive(Clone)]
+pub struct PendingSupplement {
+    pub session: UploadSession,
+    pub description: Rc<str>,
+}
+
+impl PendingCommit {
+    const STORAGE_KEY: &'static str = "upload_sessions";
+
+    fn load_all() -> BTreeMap<String, Self> {
+        LocalStorage::get(Self::STORAGE_KEY).unwrap_or_default()
+    }
+
+    fn store_all(all: &BTreeMap<String, Self>) {
+        if let Err(e) = LocalStorage::set(Self::STORAGE_KEY, all) {
+            gloo::console::error!(e.to_string());
+        }
+    }
+
+    pub fn load(thesis_id: ObjectId) -> Option<Self> {
+        Self::load_all().remove(&thesis_id.to_hex())
+    }
+
+    pub fn store(&self, thesis_id: ObjectId) {
+        let mut all = Self::load_all();
+        all.insert(thesis_id.to_hex(), self.clone());
+        Self::store_all(&all);
+    }
+
+    pub fn clear(thesis_id: ObjectId) {
+        let mut all = Self::load_all();
+        if all.remove(&thesis_id.to_hex()).is_some() {
+            Self::store_all(&all);
+        }
+    }
+
+    pub async fn try_commit(&self, cfg: &AppConfig, thesis_id: ObjectId) -> AppResult<FetchRes<ObjectId>> {
+        let body = serde_json::json!({
+            "message": self.message,
+            "revision": if self.major { "major" } else { "minor" },
+            "release": self.release._id,
+            "source": self.source.as_ref().map(|s| s._id),
+            "release_sha256": self.release.sha256,
+            "source_sha256": self.source.as_ref().map(|s| s.sha256.clone()),
+            "supplements": self.supplements.iter().map(|s| serde_json::json!({
+                "upload": s.session._id,
+                "description": s.description,
+                "sha256": s.session.sha256,
+            })).collect::<Vec<_>>(),
+        });
+        <FetchRes<ObjectId>>::try_from_gloo_res(
+            gloo::net::http::Request::post(
+                cfg.api.join(&format!("theses/{}/commit", thesis_id.to_hex()))?.as_str(),
+            )
+            .credentials(web_sys::RequestCredentials::Include)
+            .json(&body)?
+            .send()
+            .await,
+        )
+        .await
+    }
+
+    pub fn sessions(&self) -> impl Iterator<Item = &UploadSession> {
+        std::iter::once(&self.release)
+            .chain(self.source.iter())
+            .chain(self.supplements.iter().map(|s| &s.session))
+    }
+}
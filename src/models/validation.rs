@@ -0,0 +1,227 @@
+use gloo::file::{futures::read_as_bytes, Blob, File};
+
+use super::archive::{self, ArchiveError};
+
+const PDF_TAIL: u64 = 2048;
+const SOURCE_HEAD: u64 = 2 << 20;
+
+pub const SOURCE_FORMATS: [(&str, &str, &[u8]); 10] = [
+    (".tar.gz", "gzip", b"\x1f\x8b"),
+    (".tgz", "gzip", b"\x1f\x8b"),
+    (".tar.Z", "compress", b"\x1f\x9d"),
+    (".tar.bz2", "bzip2", b"BZh"),
+    (".tbz2", "bzip2", b"BZh"),
+    (".tar.lz", "lzip", b"LZIP"),
+    (".tlz", "lzip", b"LZIP"),
+    (".tar.xz", "xz", b"\xfd7zXZ\x00"),
+    (".txz", "xz", b"\xfd7zXZ\x00"),
+    (".tar.zst", "zstd", b"\x28\xb5\x2f\xfd"),
+];
+
+#[derive(thiserror::Error, Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub enum FileError {
+    #[error("{name} is empty.")]
+    Empty { name: String },
+    #[error("{name} is {size} bytes, more than the limit of {max} bytes.")]
+    TooLarge { name: String, size: u64, max: u64 },
+    #[error("{name} could not be read: {reason}")]
+    Unreadable { name: String, reason: String },
+    #[error("{name} does not start with a PDF header (%PDF-).")]
+    NotPdf { name: String },
+    #[error("{name} has no %%EOF marker; the PDF seems to be truncated.")]
+    TruncatedPdf { name: String },
+    #[error("{name} has no valid startxref offset; the PDF cannot be parsed.")]
+    BrokenPdf { name: String },
+    #[error("{name} is not one of the accepted formats: {accepted}.")]
+    UnsupportedFormat { name: String, accepted: String },
+    #[error("{name} is named like a {expected} archive but its content is not {expected}-compressed.")]
+    FormatMismatch { name: String, expected: &'static str },
+    #[error("{name} is {expected}-compressed but does not contain a tar archive.")]
+    NotTar { name: String, expected: &'static str },
+    #[error("{name} could not be decompressed: {reason}")]
+    CorruptArchive { name: String, reason: String },
+}
+
+async fn read(file: &File, start: u64, end: u64) -> Result<Vec<u8>, FileError> {
+    read_as_bytes(&Blob::from(file.slice(start, end))).await.map_err(|e| FileError::Unreadable {
+        name: file.name(),
+        reason: e.to_string(),
+    })
+}
+
+fn check_size(file: &File, max: u64) -> Result<(), FileError> {
+    match file.size() {
+        0 => Err(FileError::Empty { name: file.name() }),
+        size if size > max => Err(FileError::TooLarge { name: file.name(), size, max }),
+        _ => Ok(()),
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).rposition(|w| w == needle)
+}
+
+fn check_header(name: &str, head: &[u8]) -> Result<(), FileError> {
+    let header = find(head, b"%PDF-").ok_or_else(|| FileError::NotPdf { name: name.to_owned() })?;
+    let version = &head[header + 5..];
+    if version.len() >= 3 && version[0].is_ascii_digit() && version[1] == b'.' && version[2].is_ascii_digit() {
+        Ok(())
+    } else {
+        Err(FileError::NotPdf { name: name.to_owned() })
+    }
+}
+
+fn startxref(name: &str, tail: &[u8], size: u64) -> Result<u64, FileError> {
+    let eof = find(tail, b"%%EOF").ok_or_else(|| FileError::TruncatedPdf { name: name.to_owned() })?;
+    let startxref = find(&tail[..eof], b"startxref").ok_or_else(|| FileError::BrokenPdf { name: name.to_owned() })?;
+    std::str::from_utf8(&tail[startxref + 9..eof])
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok())
+        .filter(|offset| *offset < size)
+        .ok_or_else(|| FileError::BrokenPdf { name: name.to_owned() })
+}
+
+fn check_xref(name: &str, xref: &[u8]) -> Result<(), FileError> {
+    let xref = String::from_utf8_lossy(xref);
+    let xref = xref.trim_start();
+    let is_table = xref.starts_with("xref");
+    let is_stream = {
+        let mut words = xref.split_ascii_whitespace();
+        words.next().is_some_and(|w| w.parse::<u64>().is_ok())
+            && words.next().is_some_and(|w| w.parse::<u64>().is_ok())
+            && words.next().is_some_and(|w| w.starts_with("obj"))
+    };
+    if is_table || is_stream {
+        Ok(())
+    } else {
+        Err(FileError::BrokenPdf { name: name.to_owned() })
+    }
+}
+
+fn source_format(name: &str) -> Result<(&'static str, &'static [u8]), FileError> {
+    SOURCE_FORMATS
+        .iter()
+        .find(|(ext, _, _)| name.ends_with(ext))
+        .map(|(_, format, magic)| (*format, *magic))
+        .ok_or_else(|| FileError::UnsupportedFormat {
+            name: name.to_owned(),
+            accepted: SOURCE_FORMATS.iter().map(|(ext, _, _)| *ext).collect::<Vec<_>>().join(", "),
+        })
+}
+
+fn check_magic(name: &str, head: &[u8]) -> Result<(), FileError> {
+    let (expected, magic) = source_format(name)?;
+    if head.starts_with(magic) {
+        Ok(())
+    } else {
+        Err(FileError::FormatMismatch { name: name.to_owned(), expected })
+    }
+}
+
+fn check_archive(name: &str, head: &[u8]) -> Result<(), FileError> {
+    check_magic(name, head)?;
+    let (expected, _) = source_format(name)?;
+    match archive::check_head(head) {
+        Ok(()) | Err(ArchiveError::Unsupported(_)) => Ok(()),
+        Err(ArchiveError::Corrupt(_, reason)) => Err(FileError::CorruptArchive { name: name.to_owned(), reason }),
+        Err(_) => Err(FileError::NotTar { name: name.to_owned(), expected }),
+    }
+}
+
+pub async fn check_release(file: &File, max: u64) -> Result<(), FileError> {
+    check_size(file, max)?;
+    let name = file.name();
+    let size = file.size();
+    check_header(&name, &read(file, 0, size.min(1024)).await?)?;
+    let tail = read(file, size.saturating_sub(PDF_TAIL), size).await?;
+    let offset = startxref(&name, &tail, size)?;
+    check_xref(&name, &read(file, offset, (offset + 32).min(size)).await?)
+}
+
+pub async fn check_source(file: &File, max: u64) -> Result<(), FileError> {
+    check_size(file, max)?;
+    let name = file.name();
+    source_format(&name)?;
+    check_archive(&name, &read(file, 0, file.size().min(SOURCE_HEAD)).await?)
+}
+
+pub fn check_supplement(file: &File, max: u64) -> Result<(), FileError> {
+    check_size(file, max)
+}
+
+pub fn source_accept() -> String {
+    SOURCE_FORMATS.iter().map(|(ext, _, _)| *ext).collect::<Vec<_>>().join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PDF: &[u8] = b"%PDF-1.7\n1 0 obj\n<<>>\nendobj\nxref\n0 1\ntrailer\n<<>>\nstartxref\n28\n%%EOF\n";
+
+    #[test]
+    fn header_requires_pdf_version() {
+        assert_eq!(check_header("a.pdf", PDF), Ok(()));
+        assert_eq!(check_header("a.pdf", b"\xef\xbb\xbf%PDF-2.0"), Ok(()));
+        assert_eq!(check_header("a.pdf", b"%PDF-"), Err(FileError::NotPdf { name: "a.pdf".into() }));
+        assert_eq!(check_header("a.pdf", b"<html>"), Err(FileError::NotPdf { name: "a.pdf".into() }));
+    }
+
+    #[test]
+    fn startxref_points_into_the_file() {
+        let size = PDF.len() as u64;
+        assert_eq!(startxref("a.pdf", PDF, size), Ok(28));
+        assert_eq!(check_xref("a.pdf", &PDF[28..]), Ok(()));
+        assert_eq!(startxref("a.pdf", PDF, 20), Err(FileError::BrokenPdf { name: "a.pdf".into() }));
+    }
+
+    #[test]
+    fn truncated_pdf_has_no_eof() {
+        let truncated = &PDF[..PDF.len() - 7];
+        assert_eq!(
+            startxref("a.pdf", truncated, truncated.len() as u64),
+            Err(FileError::TruncatedPdf { name: "a.pdf".into() })
+        );
+        assert_eq!(
+            startxref("a.pdf", b"%PDF-1.4\n%%EOF", 15),
+            Err(FileError::BrokenPdf { name: "a.pdf".into() })
+        );
+    }
+
+    #[test]
+    fn xref_accepts_tables_and_streams() {
+        assert_eq!(check_xref("a.pdf", b"  xref\n0 1"), Ok(()));
+        assert_eq!(check_xref("a.pdf", b"12 0 obj\n<< /Type /XRef >>"), Ok(()));
+        assert_eq!(check_xref("a.pdf", b"garbage"), Err(FileError::BrokenPdf { name: "a.pdf".into() }));
+    }
+
+    #[test]
+    fn magic_bytes_match_extension() {
+        assert_eq!(check_magic("src.tar.gz", b"\x1f\x8b\x08"), Ok(()));
+        assert_eq!(check_magic("src.tar.zst", b"\x28\xb5\x2f\xfd"), Ok(()));
+        assert_eq!(
+            check_magic("src.tar.xz", b"\x1f\x8b\x08"),
+            Err(FileError::FormatMismatch { name: "src.tar.xz".into(), expected: "xz" })
+        );
+        assert!(matches!(check_magic("src.zip", b"PK\x03\x04"), Err(FileError::UnsupportedFormat { .. })));
+    }
+
+    #[test]
+    fn compressed_sources_must_contain_tar() {
+        let gzip = |data: &[u8]| {
+            let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            std::io::Write::write_all(&mut encoder, data).unwrap();
+            encoder.finish().unwrap()
+        };
+        let tar = archive::pack([("main.tex", &b"\\documentclass{article}"[..])], 0);
+        assert_eq!(check_archive("src.tar.gz", &gzip(&tar)), Ok(()));
+        assert_eq!(
+            check_archive("src.tar.gz", &gzip(PDF)),
+            Err(FileError::NotTar { name: "src.tar.gz".into(), expected: "gzip" })
+        );
+        assert!(matches!(check_archive("src.tgz", b"\x1f\x8b\x08garbage"), Err(FileError::CorruptArchive { .. })));
+        assert_eq!(check_archive("src.tar.Z", b"\x1f\x9d\x90"), Ok(()));
+    }
+}
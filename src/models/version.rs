@@ -21,6 +21,8 @@ pub enum ReviewPattern {
 pub struct ReviewState {
     pub remainder_reviewer_ids: Rc<BTreeSet<Rc<ObjectId>>>,
     pub pattern: ReviewPattern,
+    pub assigned_at: Option<Rc<DateTime<Utc>>>,
+    pub last_reviewed_at: Option<Rc<DateTime<Utc>>>,
 }
 
 #[derive(serde::Deserialize)]
@@ -34,6 +36,17 @@ pub enum VersionState {
     History,
 }
 
+#[derive(serde::Deserialize)]
+#[derive(PartialEq)]
+pub struct Supplement {
+    pub file_id: ObjectId,
+    pub name: Rc<str>,
+    pub description: Rc<str>,
+    pub size: u64,
+    pub mime: Rc<str>,
+    pub sha256: Option<Rc<str>>,
+}
+
 #[derive(serde::Deserialize)]
 #[serde(default)]
 #[derive(PartialEq)]
@@ -47,6 +60,10 @@ pub struct Version {
     pub minor_num: i32,
     pub file_id: ObjectId,
     pub source_id: Option<ObjectId>,
+    pub message: Rc<str>,
+    pub release_sha256: Option<Rc<str>>,
+    pub source_sha256: Option<Rc<str>>,
+    pub supplements: Rc<Vec<Supplement>>,
     pub state: VersionState,
     pub review_state: ReviewState,
     pub downloads: i32,
@@ -67,4 +84,23 @@ impl Version {
         )
         .await
     }
+
+    pub async fn try_list(
+        cfg: &AppConfig,
+        query: &[(&str, &str)],
+    ) -> AppResult<FetchRes<Rc<Vec<Rc<Self>>>>> {
+        let mut url = cfg.api.join("versions")?;
+        url.query_pairs_mut().extend_pairs(query);
+        <FetchRes<Rc<Vec<Rc<Self>>>>>::try_from_gloo_res(
+            gloo::net::http::Request::get(url.as_str())
+                .credentials(web_sys::RequestCredentials::Include)
+                .send()
+                .await,
+        )
+        .await
+    }
+
+    pub fn latest(vals: &[Rc<Self>]) -> Option<&Rc<Self>> {
+        vals.iter().max_by_key(|v| (v.major_num, v.minor_num))
+    }
 }
\ No newline at end of file
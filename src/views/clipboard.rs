@@ -0,0 +1,14 @@
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+
+use crate::models::common::AppResult;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["navigator", "clipboard"], js_name = writeText, catch)]
+    fn write_text(text: &str) -> Result<js_sys::Promise, JsValue>;
+}
+
+pub(crate) async fn copy(text: &str) -> AppResult<()> {
+    wasm_bindgen_futures::JsFuture::from(write_text(text)?).await?;
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use std::rc::Rc;
+
+use bson::oid::ObjectId;
+use yew::{function_component, html, suspense::use_future, Html, HtmlResult, Properties, Suspense};
+use yew_router::prelude::Link;
+
+use crate::{
+    models::{
+        common::{AppConfig, AppResult, FetchRes},
+        version::Version,
+    },
+    views::{alerts::AlertBox, me::with_me, Route},
+};
+
+mod queue;
+
+#[derive(PartialEq, Properties)]
+pub(crate) struct EditorProps {
+    pub(crate) cfg: Rc<AppConfig>,
+}
+
+#[function_component]
+pub(crate) fn Editor(props: &EditorProps) -> Html {
+    let cfg = props.cfg.clone();
+    let anonymous = html! {
+        <p>
+            <Link<Route> to={ Route::Login }>{ "Log in" }</Link<Route>>
+            { " to open the editor's desk." }
+        </p>
+    };
+    with_me(
+        cfg.clone(),
+        move |me| {
+            let fallback = html! {<p>{"Loading versions awaiting triage..."}</p>};
+            html! {
+                <div>
+                    <h1>{ "Editor's Desk" }</h1>
+                    <Suspense {fallback}>
+                        <Desk cfg={ cfg.clone() } me={ me.public_profile._id } />
+                    </Suspense>
+                </div>
+            }
+        },
+        anonymous,
+    )
+}
+
+#[derive(PartialEq, Properties)]
+struct DeskProps {
+    cfg: Rc<AppConfig>,
+    me: ObjectId,
+}
+
+#[function_component]
+fn Desk(props: &DeskProps) -> HtmlResult {
+    let cfg = props.cfg.clone();
+
+    let res = use_future({
+        let cfg = cfg.clone();
+        move || async move {
+            let uploaded = Version::try_list(&cfg, &[("state", "Uploaded")]).await?;
+            let reviewing = Version::try_list(&cfg, &[("state", "Reviewing")]).await?;
+            AppResult::Ok((uploaded, reviewing))
+        }
+    })?;
+    let res = match *res {
+        Ok((FetchRes::Body(ref uploaded), FetchRes::Body(ref reviewing))) => {
+            let vals = Rc::new(uploaded.iter().chain(reviewing.iter()).cloned().collect::<Vec<_>>());
+            html!(<queue::Queue {cfg} me={ props.me } {vals} />)
+        }
+        Ok((FetchRes::Other(ref other), _)) | Ok((_, FetchRes::Other(ref other))) => {
+            html!(<AlertBox refresh={ Some(Rc::new(other.to_owned())) } />)
+        }
+        Err(ref e) => e.view(),
+    };
+    Ok(res)
+}
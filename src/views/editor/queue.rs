@@ -0,0 +1,194 @@
+use std::rc::Rc;
+
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use web_sys::{Event, HtmlInputElement, HtmlSelectElement};
+use yew::{html, html_nested, Component, Context, Html, Properties, TargetCast};
+
+use crate::{
+    models::{
+        common::AppConfig,
+        thesis::Thesis,
+        version::{ReviewPattern, Version, VersionState},
+    },
+    views::{FragmentLink, Route},
+};
+
+const STALE_DAYS: i64 = 7;
+const OVERDUE_DAYS: i64 = 30;
+
+#[derive(PartialEq, Clone, Copy, Default)]
+pub(super) enum Kind {
+    #[default]
+    All,
+    Triage,
+    Decision,
+}
+
+impl Kind {
+    fn of(val: &Version) -> Option<Self> {
+        if !val.review_state.remainder_reviewer_ids.is_empty() {
+            return None;
+        }
+        match val.state {
+            VersionState::Uploaded => Some(Kind::Triage),
+            VersionState::Reviewing => Some(Kind::Decision),
+            _ => None,
+        }
+    }
+
+    fn waiting_since(self, val: &Version) -> Rc<DateTime<Utc>> {
+        let review_state = &val.review_state;
+        match self {
+            Kind::Decision => review_state
+                .last_reviewed_at
+                .clone()
+                .or_else(|| review_state.assigned_at.clone())
+                .unwrap_or_else(|| val.uploaded_at.clone()),
+            _ => val.uploaded_at.clone(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub(super) struct Queue {
+    kind: Kind,
+    mine: bool,
+    min_age: i64,
+}
+
+pub(super) enum QueueMsg {
+    Kind(Kind),
+    Mine(bool),
+    MinAge(i64),
+}
+
+#[derive(PartialEq, Properties)]
+pub(super) struct QueueProps {
+    pub(super) cfg: Rc<AppConfig>,
+    pub(super) me: ObjectId,
+    pub(super) vals: Rc<Vec<Rc<Version>>>,
+}
+
+impl Component for Queue {
+    type Message = QueueMsg;
+    type Properties = QueueProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self::default()
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            QueueMsg::Kind(kind) => self.kind = kind,
+            QueueMsg::Mine(mine) => self.mine = mine,
+            QueueMsg::MinAge(days) => self.min_age = days,
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let cfg = ctx.props().cfg.to_owned();
+        let me = ctx.props().me;
+        let now = chrono::Utc::now();
+
+        let kind = ctx.link().batch_callback(|e: Event| {
+            e.target_dyn_into::<HtmlSelectElement>().map(|s| {
+                QueueMsg::Kind(match s.value().as_str() {
+                    "triage" => Kind::Triage,
+                    "decision" => Kind::Decision,
+                    _ => Kind::All,
+                })
+            })
+        });
+        let mine = ctx.link().batch_callback(|e: Event| {
+            e.target_dyn_into::<HtmlInputElement>()
+                .map(|i| QueueMsg::Mine(i.checked()))
+        });
+        let min_age = ctx.link().batch_callback(|e: Event| {
+            e.target_dyn_into::<HtmlInputElement>()
+                .map(|i| QueueMsg::MinAge(i.value().parse().unwrap_or_default()))
+        });
+
+        let mut tasks = ctx
+            .props()
+            .vals
+            .iter()
+            .filter_map(|val| Kind::of(val).map(|kind| (kind, val)))
+            .filter(|(kind, _)| self.kind == Kind::All || self.kind == *kind)
+            .filter(|(_, val)| !self.mine || val.review_state.pattern == ReviewPattern::Editor(me))
+            .map(|(kind, val)| {
+                let since = kind.waiting_since(val);
+                (kind, val, (now - *since).num_days(), since)
+            })
+            .filter(|(_, _, age, _)| *age >= self.min_age)
+            .collect::<Vec<_>>();
+        tasks.sort_by_key(|(_, _, age, _)| -age);
+
+        let rows = tasks.into_iter().map(|(kind, val, age, since)| {
+            let to = Route::Versions { id: val._id };
+            let action = match kind {
+                Kind::Decision => html!(<FragmentLink { to } fragment="adjudge">{ "Adjudge" }</FragmentLink>),
+                _ => html!(<FragmentLink { to } fragment="edit">{ "Assign reviewers" }</FragmentLink>),
+            };
+            let age = match age {
+                age if age >= OVERDUE_DAYS => html!(<strong>{ format!("{age} days ⚠") }</strong>),
+                age if age >= STALE_DAYS => html!(<mark>{ format!("{age} days") }</mark>),
+                age => html!({ format!("{age} days") }),
+            };
+            html_nested! {
+                <tr>
+                    <td>{ Thesis::tiny_from_id(val.thesis_id, cfg.clone()) }</td>
+                    <td>{ val.view_tiny() }</td>
+                    <td>{ since.format("%Y-%m-%d").to_string() }</td>
+                    <td>{ age }</td>
+                    <td>{ action }</td>
+                </tr>
+            }
+        }).collect::<Vec<_>>();
+
+        html! {
+            <div>
+                <fieldset>
+                    <legend>{ "Filters" }</legend>
+                    <label>
+                        { "Show: " }
+                        <select onchange={ kind }>
+                            <option value="all" selected={ self.kind == Kind::All }>{ "Everything" }</option>
+                            <option value="triage" selected={ self.kind == Kind::Triage }>{ "Needs reviewers" }</option>
+                            <option value="decision" selected={ self.kind == Kind::Decision }>{ "Decision due" }</option>
+                        </select>
+                    </label>
+                    <label>
+                        { " Assigned to me only: " }
+                        <input type="checkbox" onchange={ mine } checked={ self.mine } />
+                    </label>
+                    <label>
+                        { " At least " }
+                        <input type="number" min="0" onchange={ min_age } value={ self.min_age.to_string() } />
+                        { " days old" }
+                    </label>
+                </fieldset>
+
+                if rows.is_empty() {
+                    <p>{ "Nothing is waiting for an editor." }</p>
+                } else {
+                    <table>
+                        <thead>
+                            <tr>
+                                <th>{ "Thesis" }</th>
+                                <th>{ "Version" }</th>
+                                <th>{ "Waiting since" }</th>
+                                <th>{ "Age" }</th>
+                                <th />
+                            </tr>
+                        </thead>
+                        <tbody>
+                            { for rows }
+                        </tbody>
+                    </table>
+                }
+            </div>
+        }
+    }
+}
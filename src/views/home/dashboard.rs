@@ -0,0 +1,82 @@
+use std::rc::Rc;
+
+use yew::{function_component, html, Html, Properties, Suspense};
+use yew_router::prelude::Link;
+
+use crate::{
+    models::{common::AppConfig, profile::Profile},
+    views::{self, theses::Route, transfers::Transfers},
+};
+
+use super::sections::{Awaiting, Theses, Versions};
+
+#[derive(PartialEq, Properties)]
+pub(super) struct DashboardProps {
+    pub(super) cfg: Rc<AppConfig>,
+    pub(super) me: Rc<Profile>,
+}
+
+#[function_component]
+pub(super) fn Dashboard(props: &DashboardProps) -> Html {
+    let cfg = props.cfg.clone();
+    let me = &props.me.public_profile;
+    let fallback = html! {<p>{"Loading..."}</p>};
+    html! {
+        <div>
+            <h1>
+                { "Welcome back, " }{ me.name.clone() }
+            </h1>
+
+            <section>
+                <h2>{ "My Theses" }</h2>
+                <Suspense fallback={ fallback.clone() }>
+                    <Theses cfg={ cfg.clone() } query={ vec![("owner_id", me._id.to_hex().into())] } with_latest={ true } />
+                </Suspense>
+                <p>
+                    <Link<Route> to={ Route::New }>{ "New thesis" }</Link<Route>>
+                </p>
+            </section>
+
+            <section>
+                <h2>{ "Ownership Transfers" }</h2>
+                <h3>{ "Offered to me" }</h3>
+                <Suspense fallback={ fallback.clone() }>
+                    <Transfers cfg={ cfg.clone() } me={ me._id } query={ vec![("to_id", me._id.to_hex().into())] }
+                        with_thesis={ true } placeholder={ "No thesis is being handed over to you." } />
+                </Suspense>
+                <h3>{ "Offered by me" }</h3>
+                <Suspense fallback={ fallback.clone() }>
+                    <Transfers cfg={ cfg.clone() } me={ me._id } query={ vec![("from_id", me._id.to_hex().into())] }
+                        with_thesis={ true } placeholder={ "You have no pending transfers." } />
+                </Suspense>
+            </section>
+
+            <section>
+                <h2>{ "Awaiting My Review" }</h2>
+                <Suspense fallback={ fallback.clone() }>
+                    <Versions cfg={ cfg.clone() } me={ me._id } awaiting={ Awaiting::Review } />
+                </Suspense>
+                <p>
+                    <Link<views::Route> to={ views::Route::Reviews }>{ "All my reviews" }</Link<views::Route>>
+                </p>
+            </section>
+
+            <section>
+                <h2>{ "Awaiting My Adjudication" }</h2>
+                <Suspense fallback={ fallback.clone() }>
+                    <Versions cfg={ cfg.clone() } me={ me._id } awaiting={ Awaiting::Adjudication } />
+                </Suspense>
+                <p>
+                    <Link<views::Route> to={ views::Route::Editor }>{ "Editor's desk" }</Link<views::Route>>
+                </p>
+            </section>
+
+            <section>
+                <h2>{ "Recently Passed" }</h2>
+                <Suspense {fallback}>
+                    <Theses {cfg} query={ vec![("is_passed", "true".into()), ("limit", "10".into())] } />
+                </Suspense>
+            </section>
+        </div>
+    }
+}
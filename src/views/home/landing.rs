@@ -0,0 +1,38 @@
+use std::rc::Rc;
+
+use yew::{function_component, html, Html, Properties, Suspense};
+use yew_router::prelude::Link;
+
+use crate::{models::common::AppConfig, views::Route};
+
+use super::sections::Theses;
+
+#[derive(PartialEq, Properties)]
+pub(super) struct LandingProps {
+    pub(super) cfg: Rc<AppConfig>,
+}
+
+#[function_component]
+pub(super) fn Landing(props: &LandingProps) -> Html {
+    let fallback = html! {<p>{"Loading latest theses..."}</p>};
+    html! {
+        <div>
+            <h1>{ "Prepublish" }</h1>
+
+            <p>
+                { "Share your theses before publication and get them reviewed. " }
+                <Link<Route> to={ Route::Login }>{ "Log in" }</Link<Route>>
+                { " or " }
+                <Link<Route> to={ Route::Signup }>{ "sign up" }</Link<Route>>
+                { " to take part." }
+            </p>
+
+            <section>
+                <h2>{ "Latest Passed Theses" }</h2>
+                <Suspense {fallback}>
+                    <Theses cfg={ props.cfg.clone() } query={ vec![("is_passed", "true".into()), ("limit", "20".into())] } />
+                </Suspense>
+            </section>
+        </div>
+    }
+}
@@ -0,0 +1,25 @@
+use std::rc::Rc;
+
+use yew::{function_component, html, Html, Properties};
+
+use crate::{models::common::AppConfig, views::me::with_me};
+
+mod dashboard;
+mod landing;
+mod sections;
+
+#[derive(PartialEq, Properties)]
+pub(crate) struct HomeProps {
+    pub(crate) cfg: Rc<AppConfig>,
+}
+
+#[function_component]
+pub(crate) fn Home(props: &HomeProps) -> Html {
+    let cfg = props.cfg.clone();
+    let anonymous = html!(<landing::Landing cfg={ cfg.clone() } />);
+    with_me(
+        cfg.clone(),
+        move |me| html!(<dashboard::Dashboard cfg={ cfg.clone() } {me} />),
+        anonymous,
+    )
+}
@@ -0,0 +1,154 @@
+use std::{collections::BTreeMap, rc::Rc};
+
+use bson::oid::ObjectId;
+use yew::{function_component, html, html_nested, suspense::use_future, AttrValue, HtmlResult, Properties};
+
+use crate::{
+    models::{
+        common::{AppConfig, AppResult, FetchRes},
+        thesis::Thesis,
+        version::{ReviewPattern, Version, VersionState},
+    },
+    views::alerts::AlertBox,
+};
+
+#[derive(PartialEq, Properties)]
+pub(super) struct ThesesProps {
+    pub(super) cfg: Rc<AppConfig>,
+    pub(super) query: Vec<(&'static str, AttrValue)>,
+    #[prop_or_default]
+    pub(super) with_latest: bool,
+}
+
+#[function_component]
+pub(super) fn Theses(props: &ThesesProps) -> HtmlResult {
+    let cfg = props.cfg.clone();
+    let with_latest = props.with_latest;
+
+    let res = use_future({
+        let cfg = cfg.clone();
+        let query = props.query.clone();
+        move || async move {
+            let query = query.iter().map(|(k, v)| (*k, v.as_str())).collect::<Vec<_>>();
+            let vals = match Thesis::try_list(&cfg, &query).await? {
+                FetchRes::Body(vals) => vals,
+                FetchRes::Other(other) => return AppResult::Ok(FetchRes::Other(other)),
+            };
+            if !with_latest || vals.is_empty() {
+                return Ok(FetchRes::Body((vals, Rc::default())));
+            }
+            let ids = vals.iter().map(|val| val.id._id.to_hex()).collect::<Vec<_>>();
+            let query = ids.iter().map(|id| ("thesis_id", id.as_str())).collect::<Vec<_>>();
+            Ok(match Version::try_list(&cfg, &query).await? {
+                FetchRes::Body(versions) => FetchRes::Body((vals, versions)),
+                FetchRes::Other(other) => FetchRes::Other(other),
+            })
+        }
+    })?;
+    let res = match *res {
+        Ok(ref res) => match res {
+            FetchRes::Body((vals, _)) if vals.is_empty() => html!(<p>{ "Nothing here yet." }</p>),
+            FetchRes::Body((vals, versions)) => {
+                let mut latest = BTreeMap::<ObjectId, &Rc<Version>>::new();
+                for version in versions.iter() {
+                    let entry = latest.entry(version.thesis_id).or_insert(version);
+                    if (version.major_num, version.minor_num) > (entry.major_num, entry.minor_num) {
+                        *entry = version;
+                    }
+                }
+                let items = vals.iter().map(|val| {
+                    html_nested! {
+                        <li>
+                            { val.view_tiny() }
+                            if with_latest {
+                                { " " }
+                                { match latest.get(&val.id._id) {
+                                    Some(latest) => latest.view_tiny(),
+                                    None => html!(<mark>{ "No version" }</mark>),
+                                } }
+                            }
+                        </li>
+                    }
+                });
+                html!(<ul>{ for items }</ul>)
+            }
+            FetchRes::Other(other) => html!(<AlertBox refresh={ Some(Rc::new(other.to_owned())) } />),
+        },
+        Err(ref e) => e.view(),
+    };
+    Ok(res)
+}
+
+#[derive(PartialEq, Clone, Copy)]
+pub(super) enum Awaiting {
+    Review,
+    Adjudication,
+}
+
+impl Awaiting {
+    fn query(self) -> &'static str {
+        match self {
+            Awaiting::Review => "reviewer_id",
+            Awaiting::Adjudication => "editor_id",
+        }
+    }
+
+    fn matches(self, val: &Version, me: &ObjectId) -> bool {
+        match self {
+            Awaiting::Review => val.review_state.remainder_reviewer_ids.iter().any(|id| **id == *me),
+            Awaiting::Adjudication => {
+                val.state == VersionState::Reviewing
+                    && val.review_state.remainder_reviewer_ids.is_empty()
+                    && val.review_state.pattern == ReviewPattern::Editor(*me)
+            }
+        }
+    }
+}
+
+#[derive(PartialEq, Properties)]
+pub(super) struct VersionsProps {
+    pub(super) cfg: Rc<AppConfig>,
+    pub(super) me: ObjectId,
+    pub(super) awaiting: Awaiting,
+}
+
+#[function_component]
+pub(super) fn Versions(props: &VersionsProps) -> HtmlResult {
+    let cfg = props.cfg.clone();
+    let me = props.me;
+    let awaiting = props.awaiting;
+
+    let res = use_future({
+        let cfg = cfg.clone();
+        move || async move {
+            Version::try_list(&cfg, &[(awaiting.query(), &me.to_hex())]).await
+        }
+    })?;
+    let res = match *res {
+        Ok(ref res) => match res {
+            FetchRes::Body(vals) => {
+                let mut items = vals
+                    .iter()
+                    .filter(|val| awaiting.matches(val, &me))
+                    .map(|val| {
+                        html_nested! {
+                            <li>
+                                { Thesis::tiny_from_id(val.thesis_id, cfg.clone()) }
+                                { " " }
+                                { val.view_tiny() }
+                            </li>
+                        }
+                    })
+                    .peekable();
+                if items.peek().is_none() {
+                    html!(<p>{ "Nothing is waiting for you." }</p>)
+                } else {
+                    html!(<ul>{ for items }</ul>)
+                }
+            }
+            FetchRes::Other(other) => html!(<AlertBox refresh={ Some(Rc::new(other.to_owned())) } />),
+        },
+        Err(ref e) => e.view(),
+    };
+    Ok(res)
+}
@@ -0,0 +1,135 @@
+use std::rc::Rc;
+
+use bson::oid::ObjectId;
+use yew::{html, html_nested, Callback, Component, Context, Html, Properties};
+use yew_router::prelude::Link;
+
+use crate::{
+    models::{
+        common::{AppConfig, AppError, FetchOther, FetchRes},
+        library::{Library, LibraryStore},
+    },
+    views::Route,
+};
+
+#[derive(Default)]
+pub(in crate::views) struct Bookmark {
+    store: LibraryStore,
+    val: Option<Library>,
+    diverged: bool,
+}
+
+pub(in crate::views) enum BookmarkMsg {
+    Err(AppError),
+    Alert(FetchOther),
+    Loaded(LibraryStore, Library, bool),
+    Toggle(Option<Rc<str>>),
+    Saved(Library),
+}
+
+#[derive(PartialEq, Properties)]
+pub(in crate::views) struct BookmarkProps {
+    pub(in crate::views) cfg: Rc<AppConfig>,
+    pub(in crate::views) err: Callback<AppError>,
+    pub(in crate::views) alert: Callback<FetchOther>,
+    pub(in crate::views) id: ObjectId,
+}
+
+impl Component for Bookmark {
+    type Message = BookmarkMsg;
+    type Properties = BookmarkProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_future({
+            let cfg = ctx.props().cfg.clone();
+            async move {
+                match Library::load(&cfg).await {
+                    Ok(Ok(loaded)) => BookmarkMsg::Loaded(loaded.store, loaded.val, loaded.diverged.is_some()),
+                    Ok(Err(other)) => BookmarkMsg::Alert(other),
+                    Err(e) => BookmarkMsg::Err(e),
+                }
+            }
+        });
+        Self::default()
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            BookmarkMsg::Err(e) => {
+                ctx.props().err.emit(e);
+                false
+            }
+            BookmarkMsg::Alert(other) => {
+                ctx.props().alert.emit(other);
+                false
+            }
+            BookmarkMsg::Loaded(store, val, diverged) => {
+                self.store = store;
+                self.val = Some(val);
+                self.diverged = diverged;
+                true
+            }
+            BookmarkMsg::Toggle(list) => {
+                let id = ctx.props().id;
+                if let Some(mut val) = self.val.clone() {
+                    let ids = match list {
+                        Some(name) => Rc::make_mut(&mut val.lists).entry(name).or_default(),
+                        None => Rc::make_mut(&mut val.bookmarks),
+                    };
+                    if !ids.remove(&id) {
+                        ids.insert(id);
+                    }
+                    ctx.link().send_future({
+                        let cfg = ctx.props().cfg.clone();
+                        let store = self.store;
+                        async move {
+                            match val.save(&cfg, store).await {
+                                Ok(FetchRes::Body(val)) => BookmarkMsg::Saved(val),
+                                Ok(FetchRes::Other(other)) => BookmarkMsg::Alert(other),
+                                Err(e) => BookmarkMsg::Err(e),
+                            }
+                        }
+                    });
+                }
+                false
+            }
+            BookmarkMsg::Saved(val) => {
+                self.val = Some(val);
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let val = match self.val {
+            Some(ref val) => val,
+            None => return Html::default(),
+        };
+        let id = ctx.props().id;
+        let bookmark = ctx.link().callback(|_| BookmarkMsg::Toggle(None));
+        let lists = val.lists.iter().map(|(name, ids)| {
+            let onchange = ctx.link().callback({
+                let name = name.clone();
+                move |_| BookmarkMsg::Toggle(Some(name.clone()))
+            });
+            html_nested! {
+                <label>
+                    <input type="checkbox" {onchange} checked={ ids.contains(&id) } />
+                    { name.clone() }{ " " }
+                </label>
+            }
+        });
+        html! {
+            <p>
+                <button onclick={ bookmark }>
+                    { if val.bookmarks.contains(&id) { "★ Bookmarked" } else { "☆ Bookmark" } }
+                </button>
+                { " " }
+                { for lists }
+                <Link<Route> to={ Route::Library }>
+                    { if self.diverged { "Reconcile library" } else { "Library" } }
+                </Link<Route>>
+            </p>
+        }
+    }
+}
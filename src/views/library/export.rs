@@ -0,0 +1,62 @@
+use std::{collections::BTreeSet, rc::Rc};
+
+use bson::oid::ObjectId;
+use yew::{function_component, html, suspense::use_future, AttrValue, HtmlResult, Properties};
+
+use crate::models::{
+    citation,
+    common::{AppConfig, AppResult, FetchRes},
+    thesis::Thesis,
+};
+
+#[derive(PartialEq, Properties)]
+pub(super) struct ExportProps {
+    pub(super) cfg: Rc<AppConfig>,
+    pub(super) name: AttrValue,
+    pub(super) ids: Rc<BTreeSet<ObjectId>>,
+}
+
+async fn entry(cfg: &AppConfig, id: ObjectId) -> AppResult<String> {
+    let thesis = match Thesis::try_get(cfg, id).await? {
+        FetchRes::Body(thesis) => thesis,
+        FetchRes::Other(other) => return Ok(format!("% {}: {} {}\n", id, other.status, other.msg)),
+    };
+    let sources = citation::Sources::try_load(cfg, &thesis).await?;
+    let citable = citation::Citable {
+        thesis: &thesis,
+        authors: &sources.authors,
+        version: sources.version.as_deref(),
+        url: &thesis.canonical_url(),
+    };
+    Ok(citable.bibtex())
+}
+
+#[function_component]
+pub(super) fn Export(props: &ExportProps) -> HtmlResult {
+    let cfg = props.cfg.clone();
+    let ids = props.ids.clone();
+
+    let res = use_future(move || async move {
+        let mut entries = Vec::with_capacity(ids.len());
+        for id in ids.iter() {
+            entries.push(entry(&cfg, *id).await?);
+        }
+        let bib = entries.join("\n");
+        let blob = gloo::file::Blob::new_with_options(bib.as_str(), Some("application/x-bibtex"));
+        AppResult::Ok((bib, gloo::file::ObjectUrl::from(blob)))
+    })?;
+    let res = match *res {
+        Ok((ref bib, ref href)) => {
+            html! {
+                <div>
+                    <textarea readonly={ true } value={ bib.clone() } />
+                    <p>
+                        <a href={ href.to_string() } download={ format!("{}.bib", props.name) }>{ "Download BibTeX" }</a>
+                    </p>
+                </div>
+            }
+        }
+        Err(ref e) => e.view(),
+    };
+    Ok(res)
+}
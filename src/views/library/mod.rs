@@ -0,0 +1,309 @@
+use std::{collections::BTreeSet, rc::Rc};
+
+use bson::oid::ObjectId;
+use web_sys::{HtmlInputElement, InputEvent};
+use yew::{html, html_nested, AttrValue, Component, Context, Html, Properties, Suspense, TargetCast};
+
+use crate::{
+    models::{
+        common::{AppConfig, AppError, FetchOther, FetchRes},
+        library::{Library, LibraryStore, LoadedLibrary},
+        thesis::Thesis,
+    },
+    views::alerts::AlertBox,
+};
+
+pub(in crate::views) mod bookmark;
+mod export;
+
+#[derive(Default)]
+pub(crate) struct Page {
+    err: Option<AppError>,
+    alert: Option<Rc<FetchOther>>,
+    store: LibraryStore,
+    val: Option<Library>,
+    diverged: Option<Library>,
+    name: AttrValue,
+    export: Option<(AttrValue, Rc<BTreeSet<ObjectId>>)>,
+}
+
+pub(crate) enum Resolution {
+    Account,
+    Local,
+    Merge,
+}
+
+pub(crate) enum PageMsg {
+    Err(AppError),
+    Alert(FetchOther),
+    Loaded(LoadedLibrary),
+    Resolve(Resolution),
+    Saved(Library),
+    InputName(String),
+    CreateList,
+    RemoveList(Rc<str>),
+    Remove(Option<Rc<str>>, ObjectId),
+    Export(Option<Rc<str>>),
+}
+
+#[derive(PartialEq, Properties)]
+pub(crate) struct PageProps {
+    pub(crate) cfg: Rc<AppConfig>,
+}
+
+impl Page {
+    fn save(&self, ctx: &Context<Self>, val: Library) {
+        ctx.link().send_future({
+            let cfg = ctx.props().cfg.clone();
+            let store = self.store;
+            async move {
+                match val.save(&cfg, store).await {
+                    Ok(FetchRes::Body(val)) => PageMsg::Saved(val),
+                    Ok(FetchRes::Other(other)) => PageMsg::Alert(other),
+                    Err(e) => PageMsg::Err(e),
+                }
+            }
+        });
+    }
+
+    fn view_side(&self, ctx: &Context<Self>, title: &str, val: &Library) -> Html {
+        let cfg = ctx.props().cfg.clone();
+        let theses = |ids: &BTreeSet<ObjectId>| {
+            ids.iter()
+                .map(|id| html!(<>{ " " }{ Thesis::tiny_from_id(*id, cfg.clone()) }</>))
+                .collect::<Html>()
+        };
+        let lists = val.lists.iter().map(|(name, ids)| {
+            html_nested!(<li>{ format!("List \u{201c}{name}\u{201d}:") }{ theses(ids) }</li>)
+        });
+        html! {
+            <section>
+                <h3>{ title }</h3>
+                if val.is_empty() {
+                    <p>{ "Nothing." }</p>
+                } else {
+                    <ul>
+                        if !val.bookmarks.is_empty() {
+                            <li>{ "Bookmarks:" }{ theses(&val.bookmarks) }</li>
+                        }
+                        { for lists }
+                    </ul>
+                }
+            </section>
+        }
+    }
+
+    fn view_ids(&self, ctx: &Context<Self>, list: Option<Rc<str>>, ids: &BTreeSet<ObjectId>) -> Html {
+        if ids.is_empty() {
+            return html!(<p>{ "Empty." }</p>);
+        }
+        let items = ids.iter().map(|id| {
+            let id = *id;
+            let remove = ctx.link().callback({
+                let list = list.clone();
+                move |_| PageMsg::Remove(list.clone(), id)
+            });
+            html_nested! {
+                <li>
+                    { Thesis::tiny_from_id(id, ctx.props().cfg.clone()) }
+                    <label onclick={ remove }>{ "❎" }</label>
+                </li>
+            }
+        });
+        let items = items.collect::<Vec<_>>();
+        let export = ctx.link().callback(move |_| PageMsg::Export(list.clone()));
+        html! {
+            <>
+                <ul>{ for items }</ul>
+                <button onclick={ export }>{ "Export as BibTeX" }</button>
+            </>
+        }
+    }
+}
+
+impl Component for Page {
+    type Message = PageMsg;
+    type Properties = PageProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_future({
+            let cfg = ctx.props().cfg.clone();
+            async move {
+                match Library::load(&cfg).await {
+                    Ok(Ok(loaded)) => PageMsg::Loaded(loaded),
+                    Ok(Err(other)) => PageMsg::Alert(other),
+                    Err(e) => PageMsg::Err(e),
+                }
+            }
+        });
+        Self::default()
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            PageMsg::Err(e) => {
+                self.err = Some(e);
+                true
+            }
+            PageMsg::Alert(other) => {
+                self.alert = Some(Rc::new(other));
+                true
+            }
+            PageMsg::Loaded(loaded) => {
+                self.store = loaded.store;
+                self.val = Some(loaded.val);
+                self.diverged = loaded.diverged;
+                true
+            }
+            PageMsg::Resolve(resolution) => {
+                if let (Some(val), Some(local)) = (self.val.as_ref(), self.diverged.take()) {
+                    match resolution {
+                        Resolution::Account => Library::clear_local(),
+                        Resolution::Local => self.save(ctx, local),
+                        Resolution::Merge => self.save(ctx, val.merge(&local)),
+                    }
+                }
+                true
+            }
+            PageMsg::Saved(val) => {
+                if self.store == LibraryStore::Account {
+                    Library::clear_local();
+                }
+                self.val = Some(val);
+                true
+            }
+            PageMsg::InputName(name) => {
+                self.name = name.into();
+                true
+            }
+            PageMsg::CreateList => {
+                if let Some(mut val) = self.val.clone() {
+                    Rc::make_mut(&mut val.lists).entry(self.name.as_str().into()).or_default();
+                    self.name = AttrValue::default();
+                    self.save(ctx, val);
+                }
+                true
+            }
+            PageMsg::RemoveList(name) => {
+                if let Some(mut val) = self.val.clone() {
+                    Rc::make_mut(&mut val.lists).remove(&name);
+                    self.save(ctx, val);
+                }
+                false
+            }
+            PageMsg::Remove(list, id) => {
+                if let Some(mut val) = self.val.clone() {
+                    match list {
+                        Some(name) => {
+                            if let Some(ids) = Rc::make_mut(&mut val.lists).get_mut(&name) {
+                                ids.remove(&id);
+                            }
+                        }
+                        None => {
+                            Rc::make_mut(&mut val.bookmarks).remove(&id);
+                        }
+                    }
+                    self.save(ctx, val);
+                }
+                false
+            }
+            PageMsg::Export(list) => {
+                self.export = self.val.as_ref().and_then(|val| match list {
+                    Some(name) => val
+                        .lists
+                        .get(&name)
+                        .map(|ids| (AttrValue::from(name.to_string()), Rc::new(ids.clone()))),
+                    None => Some(("bookmarks".into(), val.bookmarks.clone())),
+                });
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if let Some(ref err) = self.err {
+            return err.view();
+        }
+        let val = match self.val {
+            Some(ref val) => val,
+            None => return html!(<div>{ "Loading library..." }</div>),
+        };
+
+        let merge = self.diverged.as_ref().map(|local| {
+            let account = ctx.link().callback(|_| PageMsg::Resolve(Resolution::Account));
+            let keep_local = ctx.link().callback(|_| PageMsg::Resolve(Resolution::Local));
+            let merge = ctx.link().callback(|_| PageMsg::Resolve(Resolution::Merge));
+            html! {
+                <dialog open={ true }>
+                    <p>{ "This browser holds bookmarks and reading lists that differ from your account." }</p>
+                    { self.view_side(ctx, "Only in your account", &val.difference(local)) }
+                    { self.view_side(ctx, "Only in this browser", &local.difference(val)) }
+                    <p>
+                        <small>
+                            { "Merging keeps everything listed above, including entries removed on the other side." }
+                        </small>
+                    </p>
+                    <button onclick={ merge }>{ "Merge both" }</button>
+                    <button onclick={ account }>{ "Keep account copy" }</button>
+                    <button onclick={ keep_local }>{ "Keep this browser's copy" }</button>
+                </dialog>
+            }
+        });
+        let input_name = ctx.link().batch_callback(|e: InputEvent| {
+            e.target_dyn_into::<HtmlInputElement>()
+                .map(|i| PageMsg::InputName(i.value()))
+        });
+        let create = ctx.link().callback(|_| PageMsg::CreateList);
+        let lists = val.lists.iter().map(|(name, ids)| {
+            let remove = ctx.link().callback({
+                let name = name.clone();
+                move |_| PageMsg::RemoveList(name.clone())
+            });
+            html_nested! {
+                <section>
+                    <h2>
+                        { name.clone() }
+                        <label onclick={ remove }>{ "❎" }</label>
+                    </h2>
+                    { self.view_ids(ctx, Some(name.clone()), ids) }
+                </section>
+            }
+        });
+        let export = self.export.as_ref().map(|(name, ids)| {
+            let fallback = html! {<p>{ "Preparing BibTeX..." }</p>};
+            html! {
+                <section>
+                    <h2>{ format!("Export: {name}") }</h2>
+                    <Suspense {fallback}>
+                        <export::Export key={ name.to_string() } cfg={ ctx.props().cfg.clone() } name={ name.clone() } ids={ ids.clone() } />
+                    </Suspense>
+                </section>
+            }
+        });
+        let alert_box = html_nested!(<AlertBox refresh={ self.alert.clone() } />);
+        html! {
+            <div>
+                <h1>{ "Library" }</h1>
+                if self.store == LibraryStore::Local {
+                    <p><small>{ "Saved in this browser only. Log in to keep it with your account." }</small></p>
+                }
+
+                <section>
+                    <h2>{ "Bookmarks" }</h2>
+                    { self.view_ids(ctx, None, &val.bookmarks) }
+                </section>
+
+                { for lists }
+
+                <p>
+                    <input type="text" placeholder="New reading list" oninput={ input_name } value={ self.name.clone() } />
+                    <button onclick={ create } disabled={ self.name.is_empty() }>{ "Create" }</button>
+                </p>
+
+                { for export }
+                { for merge }
+                { alert_box }
+            </div>
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use pulldown_cmark::{Event, Options, Parser, Tag};
+use yew::{html, AttrValue, Html};
+
+use crate::models::math;
+
+fn is_safe_url(url: &str) -> bool {
+    match url.split_once(':') {
+        Some((scheme, _)) if !scheme.contains(['/', '?', '#']) => {
+            ["http", "https", "mailto"].iter().any(|s| scheme.eq_ignore_ascii_case(s))
+        }
+        _ => true,
+    }
+}
+
+fn element(tag: Tag, children: Vec<Html>) -> Html {
+    match tag {
+        Tag::Paragraph => html!(<p>{ for children }</p>),
+        Tag::Heading { level, .. } => html!(<@{ level.to_string() }>{ for children }</@>),
+        Tag::BlockQuote(_) => html!(<blockquote>{ for children }</blockquote>),
+        Tag::CodeBlock(_) => html!(<pre><code>{ for children }</code></pre>),
+        Tag::List(Some(start)) => html!(<ol start={ start.to_string() }>{ for children }</ol>),
+        Tag::List(None) => html!(<ul>{ for children }</ul>),
+        Tag::Item => html!(<li>{ for children }</li>),
+        Tag::Emphasis => html!(<em>{ for children }</em>),
+        Tag::Strong => html!(<strong>{ for children }</strong>),
+        Tag::Strikethrough => html!(<del>{ for children }</del>),
+        Tag::Link { dest_url, title, .. } | Tag::Image { dest_url, title, .. } if is_safe_url(&dest_url) => {
+            html! {
+                <a href={ dest_url.to_string() } title={ (!title.is_empty()).then(|| title.to_string()) } rel="nofollow noopener">
+                    { for children }
+                </a>
+            }
+        }
+        _ => html!(<>{ for children }</>),
+    }
+}
+
+pub(crate) fn view(src: &str) -> Html {
+    let mut stack = vec![(None, Vec::new())];
+    for event in Parser::new_ext(src, Options::ENABLE_MATH | Options::ENABLE_STRIKETHROUGH) {
+        match event {
+            Event::Start(tag) => stack.push((Some(tag), Vec::new())),
+            Event::End(_) => {
+                if let Some((Some(tag), children)) = stack.pop() {
+                    if let Some((_, parent)) = stack.last_mut() {
+                        parent.push(element(tag, children));
+                    }
+                }
+            }
+            event => {
+                let node = match event {
+                    Event::Text(text) | Event::Html(text) | Event::InlineHtml(text) => html!({ text.to_string() }),
+                    Event::Code(code) => html!(<code>{ code.to_string() }</code>),
+                    Event::InlineMath(tex) => Html::from_html_unchecked(AttrValue::from(math::to_mathml(&tex, false))),
+                    Event::DisplayMath(tex) => Html::from_html_unchecked(AttrValue::from(math::to_mathml(&tex, true))),
+                    Event::SoftBreak => html!({ " " }),
+                    Event::HardBreak => html!(<br />),
+                    Event::Rule => html!(<hr />),
+                    _ => continue,
+                };
+                if let Some((_, parent)) = stack.last_mut() {
+                    parent.push(node);
+                }
+            }
+        }
+    }
+    let children = stack.into_iter().next().map(|(_, children)| children).unwrap_or_default();
+    html!(<div class="markup">{ for children }</div>)
+}
+
+pub(crate) fn preview(src: &str) -> Html {
+    html! {
+        <output>
+            if src.trim().is_empty() {
+                <small>{ "Preview: Markdown with $inline$ and $$display$$ LaTeX math." }</small>
+            } else {
+                { view(src) }
+            }
+        </output>
+    }
+}
@@ -0,0 +1,47 @@
+use std::rc::Rc;
+
+use yew::{function_component, html, suspense::use_future, Callback, Html, HtmlResult, Properties, Suspense};
+
+use crate::{
+    models::{
+        common::{AppConfig, FetchRes},
+        profile::Profile,
+    },
+    views::alerts::AlertBox,
+};
+
+pub(crate) fn with_me(cfg: Rc<AppConfig>, render: impl Fn(Rc<Profile>) -> Html + 'static, anonymous: Html) -> Html {
+    let fallback = html! {<div>{"Loading..."}</div>};
+    let render = Callback::from(render);
+
+    html! {
+        <Suspense {fallback}>
+            <Me {cfg} {render} {anonymous} />
+        </Suspense>
+    }
+}
+
+#[derive(PartialEq, Properties)]
+struct MeProps {
+    cfg: Rc<AppConfig>,
+    render: Callback<Rc<Profile>, Html>,
+    anonymous: Html,
+}
+
+#[function_component]
+fn Me(props: &MeProps) -> HtmlResult {
+    let cfg = props.cfg.clone();
+
+    let res = use_future(move || async move {
+        Profile::try_get_self(&cfg).await
+    })?;
+    let res = match *res {
+        Ok(ref res) => match res {
+            FetchRes::Body(me) => props.render.emit(me.clone()),
+            FetchRes::Other(other) if matches!(other.status, 401 | 403) => props.anonymous.clone(),
+            FetchRes::Other(other) => html!(<AlertBox refresh={ Some(Rc::new(other.to_owned())) } />),
+        },
+        Err(ref e) => e.view(),
+    };
+    Ok(res)
+}
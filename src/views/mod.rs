@@ -9,8 +9,20 @@ mod tiny_profiles;
 mod signup;
 mod login;
 mod profile_list;
+mod home;
+mod tiny_theses;
+mod tiny_versions;
+mod me;
+mod reviews;
+mod editor;
+mod library;
+mod clipboard;
+mod markup;
+mod share;
+mod sizes;
+mod transfers;
 
-pub(crate) use route::Route;
+pub(crate) use route::{FragmentLink, Route};
 
 impl Route {
     pub(super) fn switch(cfg: std::rc::Rc<crate::models::common::AppConfig>) -> impl Fn(Self) -> Html {
@@ -19,9 +31,7 @@ impl Route {
                 <yew_router::Switch<theses::Route> render={ theses::Route::switch(cfg.clone()) }/>
             },
             Self::Home => html! {
-                <div>
-                    { "WIP" }
-                </div>
+                <home::Home cfg={ cfg.clone() } />
             },
             Self::Signup => html! {
                 <signup::Signup cfg={ cfg.clone() } />
@@ -29,6 +39,15 @@ impl Route {
             Self::Login => html! {
                 <login::Login cfg={ cfg.clone() } />
             },
+            Self::Reviews => html! {
+                <reviews::Reviews cfg={ cfg.clone() } />
+            },
+            Self::Editor => html! {
+                <editor::Editor cfg={ cfg.clone() } />
+            },
+            Self::Library => html! {
+                <library::Page cfg={ cfg.clone() } />
+            },
             Self::Versions { id } => html! {
                 <versions::Get cfg={ cfg.clone() } {id} />
             },
@@ -18,6 +18,8 @@ pub(in crate::views) enum InputMsg {
 pub(in crate::views) struct InputProps {
     pub(in crate::views) validate: Callback<ObjectId, bool>,
     pub(in crate::views) submit: Callback<ObjectId>,
+    #[prop_or(AttrValue::Static("Add"))]
+    pub(in crate::views) action: AttrValue,
 }
 
 impl Component for Input {
@@ -58,9 +60,9 @@ impl Component for Input {
                         InputMsg::Submit
                     }
                 });
-                html_nested!(<button {onclick} disabled={!ctx.props().validate.emit(id)}>{"Add"}</button>)
+                html_nested!(<button {onclick} disabled={!ctx.props().validate.emit(id)}>{ ctx.props().action.clone() }</button>)
             }
-            None => html_nested!(<button disabled={ true }>{"Add"}</button>)
+            None => html_nested!(<button disabled={ true }>{ ctx.props().action.clone() }</button>)
         };
         html! {
             <div>
@@ -31,14 +31,18 @@ pub(in crate::views) struct AuthorsProps {
     pub(in crate::views) err: Callback<AppError>,
     pub(in crate::views) alert: Callback<FetchOther>,
     pub(in crate::views) vals: Callback<Rc<Vec<Rc<PublicProfile>>>>,
+    #[prop_or_default]
+    pub(in crate::views) init: Rc<Vec<Rc<PublicProfile>>>,
 }
 
 impl Component for ProfileList {
     type Message = AuthorsMsg;
     type Properties = AuthorsProps;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        Self::default()
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            vals: ctx.props().init.clone(),
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -0,0 +1,111 @@
+use std::rc::Rc;
+
+use bson::oid::ObjectId;
+use yew::{function_component, html, html_nested, suspense::use_future, Html, HtmlResult, Properties, Suspense};
+use yew_router::prelude::Link;
+
+use crate::{
+    models::{
+        common::{AppConfig, FetchRes},
+        thesis::Thesis,
+        version::Version,
+    },
+    views::{alerts::AlertBox, me::with_me, FragmentLink, Route},
+};
+
+#[derive(PartialEq, Properties)]
+pub(crate) struct ReviewsProps {
+    pub(crate) cfg: Rc<AppConfig>,
+}
+
+#[function_component]
+pub(crate) fn Reviews(props: &ReviewsProps) -> Html {
+    let cfg = props.cfg.clone();
+    let anonymous = html! {
+        <p>
+            <Link<Route> to={ Route::Login }>{ "Log in" }</Link<Route>>
+            { " to see the versions assigned to you." }
+        </p>
+    };
+    with_me(
+        cfg.clone(),
+        move |me| {
+            let fallback = html! {<p>{"Loading assigned versions..."}</p>};
+            html! {
+                <div>
+                    <h1>{ "My Reviews" }</h1>
+                    <Suspense {fallback}>
+                        <Queue cfg={ cfg.clone() } me={ me.public_profile._id } />
+                    </Suspense>
+                </div>
+            }
+        },
+        anonymous,
+    )
+}
+
+#[derive(PartialEq, Properties)]
+struct QueueProps {
+    cfg: Rc<AppConfig>,
+    me: ObjectId,
+}
+
+#[function_component]
+fn Queue(props: &QueueProps) -> HtmlResult {
+    let cfg = props.cfg.clone();
+    let me = props.me;
+
+    let res = use_future({
+        let cfg = cfg.clone();
+        move || async move {
+            Version::try_list(&cfg, &[("reviewer_id", &me.to_hex())]).await
+        }
+    })?;
+    let res = match *res {
+        Ok(ref res) => match res {
+            FetchRes::Body(vals) => {
+                let mut vals = vals
+                    .iter()
+                    .filter(|val| val.review_state.remainder_reviewer_ids.iter().any(|id| **id == me))
+                    .collect::<Vec<_>>();
+                vals.sort_by_key(|val| val.review_state.assigned_at.clone());
+                if vals.is_empty() {
+                    html!(<p>{ "No version is waiting for your review." }</p>)
+                } else {
+                    let rows = vals.into_iter().map(|val| {
+                        html_nested! {
+                            <tr>
+                                <td>{ Thesis::tiny_from_id(val.thesis_id, cfg.clone()) }</td>
+                                <td>{ val.view_tiny() }</td>
+                                <td>
+                                    { val.review_state.assigned_at.as_ref().map(|at| at.format("%Y-%m-%d").to_string()).unwrap_or_default() }
+                                </td>
+                                <td>
+                                    <FragmentLink to={ Route::Versions { id: val._id } } fragment="review">{ "Review" }</FragmentLink>
+                                </td>
+                            </tr>
+                        }
+                    });
+                    html! {
+                        <table>
+                            <thead>
+                                <tr>
+                                    <th>{ "Thesis" }</th>
+                                    <th>{ "Version" }</th>
+                                    <th>{ "Assigned" }</th>
+                                    <th />
+                                </tr>
+                            </thead>
+                            <tbody>
+                                { for rows }
+                            </tbody>
+                        </table>
+                    }
+                }
+            }
+            FetchRes::Other(other) => html!(<AlertBox refresh={ Some(Rc::new(other.to_owned())) } />),
+        },
+        Err(ref e) => e.view(),
+    };
+    Ok(res)
+}
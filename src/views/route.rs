@@ -1,3 +1,6 @@
+use yew::{function_component, html, Callback, Children, Html, MouseEvent, Properties};
+use yew_router::{hooks::use_navigator, Routable};
+
 #[derive(Clone, yew_router::Routable, PartialEq)]
 pub enum Route {
     #[at("/")]
@@ -10,6 +13,41 @@ pub enum Route {
     ThesesRoot,
     #[at("/theses/*")]
     Theses,
+    #[at("/reviews")]
+    Reviews,
+    #[at("/editor")]
+    Editor,
+    #[at("/library")]
+    Library,
     #[at("/versions/:id")]
     Versions { id: bson::oid::ObjectId },
-}
\ No newline at end of file
+}
+
+#[derive(Clone, PartialEq)]
+pub(crate) struct Fragment(pub(crate) &'static str);
+
+#[derive(PartialEq, Properties)]
+pub(crate) struct FragmentLinkProps {
+    pub(crate) to: Route,
+    pub(crate) fragment: &'static str,
+    pub(crate) children: Children,
+}
+
+#[function_component]
+pub(crate) fn FragmentLink(props: &FragmentLinkProps) -> Html {
+    let navigator = use_navigator();
+    let href = format!("{}#{}", props.to.to_path(), props.fragment);
+    let onclick = {
+        let to = props.to.clone();
+        let fragment = props.fragment;
+        Callback::from(move |e: MouseEvent| {
+            if let Some(navigator) = &navigator {
+                e.prevent_default();
+                navigator.push_with_state(&to, Fragment(fragment));
+            }
+        })
+    };
+    html! {
+        <a { href } { onclick }>{ props.children.clone() }</a>
+    }
+}
@@ -0,0 +1,198 @@
+use qrcode::{render::svg, QrCode};
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+use yew::{html, html_nested, AttrValue, Component, Context, Html, Properties};
+
+use crate::{models::common::AppResult, views::clipboard};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = navigator, js_name = share, catch)]
+    fn navigator_share(data: &JsValue) -> Result<js_sys::Promise, JsValue>;
+}
+
+fn can_share() -> bool {
+    js_sys::Reflect::get(&js_sys::global(), &"navigator".into())
+        .and_then(|navigator| js_sys::Reflect::has(&navigator, &"share".into()))
+        .unwrap_or(false)
+}
+
+async fn share(title: &str, url: &str) -> AppResult<()> {
+    let data = js_sys::Object::new();
+    js_sys::Reflect::set(&data, &"title".into(), &title.into())?;
+    js_sys::Reflect::set(&data, &"url".into(), &url.into())?;
+    wasm_bindgen_futures::JsFuture::from(navigator_share(&data)?).await?;
+    Ok(())
+}
+
+fn qr_svg(url: &str) -> Option<String> {
+    let svg = QrCode::new(url.as_bytes())
+        .ok()?
+        .render::<svg::Color>()
+        .min_dimensions(200, 200)
+        .quiet_zone(true)
+        .build();
+    svg.find("<svg").map(|start| svg[start..].to_owned())
+}
+
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub(crate) enum Link {
+    Canonical,
+    Pinned,
+}
+
+impl Link {
+    fn label(self) -> &'static str {
+        match self {
+            Link::Canonical => "Canonical link",
+            Link::Pinned => "Version link",
+        }
+    }
+}
+
+pub(crate) struct Share {
+    link: Link,
+    qr: Option<(AttrValue, gloo::file::ObjectUrl)>,
+    copied: Option<Result<Link, String>>,
+    can_share: bool,
+}
+
+pub(crate) enum ShareMsg {
+    Select(Link),
+    Copy(Link),
+    Copied(Result<Link, String>),
+    Share,
+}
+
+#[derive(PartialEq, Properties)]
+pub(crate) struct ShareProps {
+    pub(crate) title: AttrValue,
+    pub(crate) canonical: AttrValue,
+    #[prop_or_default]
+    pub(crate) pinned: Option<AttrValue>,
+}
+
+impl Share {
+    fn url(ctx: &Context<Self>, link: Link) -> AttrValue {
+        match (link, &ctx.props().pinned) {
+            (Link::Pinned, Some(pinned)) => pinned.clone(),
+            _ => ctx.props().canonical.clone(),
+        }
+    }
+
+    fn render_qr(&mut self, ctx: &Context<Self>) {
+        self.qr = qr_svg(&Self::url(ctx, self.link)).map(|svg| {
+            let blob = gloo::file::Blob::new_with_options(svg.as_str(), Some("image/svg+xml"));
+            (svg.into(), gloo::file::ObjectUrl::from(blob))
+        });
+    }
+}
+
+impl Component for Share {
+    type Message = ShareMsg;
+    type Properties = ShareProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let mut share = Self {
+            link: match ctx.props().pinned {
+                Some(_) => Link::Pinned,
+                None => Link::Canonical,
+            },
+            qr: None,
+            copied: None,
+            can_share: can_share(),
+        };
+        share.render_qr(ctx);
+        share
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        if ctx.props().pinned.is_none() {
+            self.link = Link::Canonical;
+        }
+        self.render_qr(ctx);
+        true
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            ShareMsg::Select(link) => {
+                self.link = link;
+                self.copied = None;
+                self.render_qr(ctx);
+                true
+            }
+            ShareMsg::Copy(link) => {
+                let url = Self::url(ctx, link);
+                ctx.link().send_future(async move {
+                    ShareMsg::Copied(clipboard::copy(&url).await.map(|_| link).map_err(|e| e.to_string()))
+                });
+                false
+            }
+            ShareMsg::Copied(copied) => {
+                self.copied = Some(copied);
+                true
+            }
+            ShareMsg::Share => {
+                let title = ctx.props().title.clone();
+                let url = Self::url(ctx, self.link);
+                wasm_bindgen_futures::spawn_local(async move {
+                    if let Err(e) = share(&title, &url).await {
+                        gloo::console::warn!(e.to_string());
+                    }
+                });
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let links = [Link::Canonical, Link::Pinned]
+            .into_iter()
+            .filter(|link| *link == Link::Canonical || ctx.props().pinned.is_some())
+            .map(|link| {
+                let url = Self::url(ctx, link);
+                let status = match self.copied {
+                    Some(Ok(copied)) if copied == link => html!(<small>{ " Copied!" }</small>),
+                    Some(Err(ref e)) => html!(<small>{ format!(" Copy failed: {e}") }</small>),
+                    _ => html!(),
+                };
+                html_nested! {
+                    <p>
+                        <label>
+                            <input type="radio" name="share" checked={ self.link == link }
+                                onchange={ ctx.link().callback(move |_| ShareMsg::Select(link)) } />
+                            { link.label() }{ ": " }
+                        </label>
+                        <input type="url" readonly={ true } value={ url.clone() } />
+                        <button onclick={ ctx.link().callback(move |_| ShareMsg::Copy(link)) }>{ "Copy" }</button>
+                        { status }
+                    </p>
+                }
+            });
+        let file = format!("qr-{}.svg", match self.link {
+            Link::Canonical => "thesis",
+            Link::Pinned => "version",
+        });
+        html! {
+            <section>
+                <h2>{ "Share" }</h2>
+                { for links }
+                if self.can_share {
+                    <p>
+                        <button onclick={ ctx.link().callback(|_| ShareMsg::Share) }>{ "Share..." }</button>
+                    </p>
+                }
+                if let Some((ref svg, ref href)) = self.qr {
+                    <figure>
+                        { Html::from_html_unchecked(svg.clone()) }
+                        <figcaption>
+                            { format!("QR code for the {}. ", self.link.label().to_lowercase()) }
+                            <a href={ (**href).to_owned() } download={ file }>{ "Download SVG" }</a>
+                        </figcaption>
+                    </figure>
+                }
+            </section>
+        }
+    }
+}
@@ -0,0 +1,13 @@
+pub(crate) fn format(size: u64) -> String {
+    const UNITS: [&str; 4] = ["bytes", "KiB", "MiB", "GiB"];
+    let mut size = size as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{} {}", size, UNITS[unit]),
+        _ => format!("{:.1} {}", size, UNITS[unit]),
+    }
+}
@@ -0,0 +1,89 @@
+use std::rc::Rc;
+
+use web_sys::{Event, HtmlSelectElement};
+use yew::{html, html_nested, Component, Context, Html, Properties, TargetCast};
+
+use crate::models::version::Version;
+
+pub(super) struct Changelog {
+    from: usize,
+    to: usize,
+}
+
+pub(super) enum ChangelogMsg {
+    From(usize),
+    To(usize),
+}
+
+#[derive(PartialEq, Properties)]
+pub(super) struct ChangelogProps {
+    pub(super) vals: Rc<Vec<Rc<Version>>>,
+}
+
+impl Component for Changelog {
+    type Message = ChangelogMsg;
+    type Properties = ChangelogProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            from: 0,
+            to: ctx.props().vals.len().saturating_sub(1),
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            ChangelogMsg::From(from) => self.from = from,
+            ChangelogMsg::To(to) => self.to = to,
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let vals = &ctx.props().vals;
+        let select = |selected: usize, msg: fn(usize) -> ChangelogMsg| {
+            let onchange = ctx.link().batch_callback(move |e: Event| {
+                e.target_dyn_into::<HtmlSelectElement>()
+                    .and_then(|s| s.value().parse().ok())
+                    .map(msg)
+            });
+            let options = vals.iter().enumerate().map(|(i, val)| {
+                html_nested! {
+                    <option value={ i.to_string() } selected={ i == selected }>
+                        { val.major_num }{ "." }{ val.minor_num }
+                    </option>
+                }
+            });
+            html!(<select {onchange}>{ for options }</select>)
+        };
+        let (from, to) = (self.from.min(self.to), self.from.max(self.to));
+        let entries = vals
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| from < *i && *i <= to)
+            .map(|(_, val)| {
+                html_nested! {
+                    <li>
+                        { val.view_tiny() }
+                        { " " }{ val.uploaded_at.format("%Y-%m-%d").to_string() }
+                        <blockquote>{ &*val.message }</blockquote>
+                    </li>
+                }
+            })
+            .collect::<Vec<_>>();
+        html! {
+            <details>
+                <summary>{ "Changelog" }</summary>
+                <p>
+                    { "From " }{ select(self.from, ChangelogMsg::From) }
+                    { " to " }{ select(self.to, ChangelogMsg::To) }
+                </p>
+                if entries.is_empty() {
+                    <p>{ "Choose two different versions to see what changed between them." }</p>
+                } else {
+                    <ol>{ entries }</ol>
+                }
+            </details>
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use std::rc::Rc;
+
+use yew::{function_component, html, html_nested, suspense::use_future_with_deps, use_state, Callback, HtmlResult, Properties};
+
+use crate::{
+    models::{
+        citation::{Citable, Sources, Style},
+        common::{AppConfig, AppResult},
+        thesis::Thesis,
+        version::Version,
+    },
+    views::clipboard,
+};
+
+#[derive(PartialEq, Properties)]
+pub(super) struct CiteProps {
+    pub(super) cfg: Rc<AppConfig>,
+    pub(super) val: Rc<Thesis>,
+    pub(super) version: Option<Rc<Version>>,
+}
+
+#[function_component]
+pub(super) fn Cite(props: &CiteProps) -> HtmlResult {
+    let style = use_state(|| Style::Apa);
+    let copied = use_state(|| None::<Result<Style, String>>);
+
+    let res = use_future_with_deps({
+        let cfg = props.cfg.clone();
+        move |deps: Rc<(Rc<Thesis>, Option<Rc<Version>>)>| async move {
+            let (ref val, ref version) = *deps;
+            let authors = Sources::try_load_authors(&cfg, val).await?;
+            let url = val.canonical_url();
+            let citable = Citable {
+                thesis: val,
+                authors: &authors,
+                version: version.as_deref(),
+                url: &url,
+            };
+            let citations = Style::ALL
+                .into_iter()
+                .map(|style| {
+                    let text = citable.render(style);
+                    let href = style.file().map(|(_, mime)| {
+                        gloo::file::ObjectUrl::from(gloo::file::Blob::new_with_options(text.as_str(), Some(mime)))
+                    });
+                    (style, text, href)
+                })
+                .collect::<Vec<_>>();
+            AppResult::Ok(citations)
+        }
+    }, (props.val.clone(), props.version.clone()))?;
+    let citations = match *res {
+        Ok(ref citations) => citations,
+        Err(ref e) => return Ok(e.view()),
+    };
+
+    let tabs = Style::ALL.into_iter().map(|s| {
+        let onclick = Callback::from({
+            let style = style.clone();
+            let copied = copied.clone();
+            move |_| {
+                style.set(s);
+                copied.set(None);
+            }
+        });
+        html_nested!(<button {onclick} disabled={ *style == s }>{ s.label() }</button>)
+    });
+    let (_, ref text, ref href) = match citations.iter().find(|(s, _, _)| *s == *style) {
+        Some(citation) => citation,
+        None => return Ok(html!()),
+    };
+    let copy = Callback::from({
+        let style = *style;
+        let text = text.clone();
+        let copied = copied.clone();
+        move |_| {
+            let text = text.clone();
+            let copied = copied.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                copied.set(Some(clipboard::copy(&text).await.map(|_| style).map_err(|e| e.to_string())));
+            });
+        }
+    });
+    let status = match *copied {
+        Some(Ok(s)) if s == *style => html!(<small>{ " Copied!" }</small>),
+        Some(Err(ref e)) => html!(<small>{ format!(" Copy failed: {e}") }</small>),
+        _ => html!(),
+    };
+    let file = style.file().map(|(ext, _)| format!("{}.{}", props.val.id._id.to_hex(), ext));
+
+    Ok(html! {
+        <div>
+            <menu>
+                { for tabs }
+            </menu>
+            if file.is_some() {
+                <textarea readonly={ true } value={ text.clone() } />
+            } else {
+                <blockquote>{ text }</blockquote>
+            }
+            <p>
+                <button onclick={ copy }>{ "Copy" }</button>
+                if let (Some(href), Some(file)) = (href, file) {
+                    { " " }
+                    <a href={ href.to_string() } download={ file }>{ format!("Download {}", style.label()) }</a>
+                }
+                { status }
+            </p>
+        </div>
+    })
+}
@@ -0,0 +1,35 @@
+use gloo::file::{File, ObjectUrl};
+use yew::{html, Html};
+
+use crate::{models::validation::SOURCE_FORMATS, views::sizes};
+
+use super::Part;
+
+pub(super) fn classify(file: &File) -> Option<Part> {
+    let name = file.name();
+    if file.raw_mime_type() == mime::APPLICATION_PDF.essence_str() || name.to_lowercase().ends_with(".pdf") {
+        Some(Part::Release)
+    } else if SOURCE_FORMATS.iter().any(|(ext, _, _)| name.ends_with(ext)) {
+        Some(Part::Source)
+    } else {
+        None
+    }
+}
+
+pub(super) fn view_file(file: &File, thumbnail: Option<&ObjectUrl>) -> Html {
+    let preview = match thumbnail {
+        Some(url) => html! {
+            <object data={ format!("{}#page=1&toolbar=0&view=Fit", &**url) } type={ mime::APPLICATION_PDF.to_string() }
+                width="120" height="160">
+                { "📄" }
+            </object>
+        },
+        None => html!(<span>{ "🗜️" }</span>),
+    };
+    html! {
+        <figure>
+            { preview }
+            <figcaption>{ file.name() }{ " (" }{ sizes::format(file.size()) }{ ")" }</figcaption>
+        </figure>
+    }
+}
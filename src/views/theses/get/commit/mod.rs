@@ -0,0 +1,673 @@
+use std::rc::Rc;
+
+use bson::oid::ObjectId;
+use gloo::file::File;
+use web_sys::{AbortController, DragEvent, Event, HtmlInputElement, HtmlTextAreaElement, InputEvent, SubmitEvent};
+use yew::{html, html_nested, AttrValue, Callback, Component, Context, Html, Properties, TargetCast};
+
+use crate::{models::{
+    common::{AppConfig, AppError, AppResult, FetchOther, FetchRes},
+    digest,
+    upload::{PendingCommit, PendingSupplement, UploadSession},
+    validation::{self, FileError},
+    version::{Version, VersionState},
+}, views::sizes};
+
+mod drop;
+mod upload;
+
+struct Uploading {
+    abort: AbortController,
+    release: u64,
+    source: u64,
+    supplements: Vec<u64>,
+}
+
+struct Supplement {
+    file: File,
+    description: AttrValue,
+    check: Option<Result<Rc<str>, FileError>>,
+}
+
+#[derive(Default)]
+pub(super) struct Commit {
+    latest: Option<Rc<Version>>,
+    major: bool,
+    message: AttrValue,
+    release: Option<File>,
+    source: Option<File>,
+    release_check: Option<Result<Rc<str>, FileError>>,
+    source_check: Option<Result<Rc<str>, FileError>>,
+    thumbnail: Option<gloo::file::ObjectUrl>,
+    supplements: Vec<Supplement>,
+    pending: Option<PendingCommit>,
+    uploading: Option<Uploading>,
+}
+
+#[derive(Clone, Copy)]
+pub(super) enum Part {
+    Release,
+    Source,
+    Supplement(usize),
+}
+
+pub(super) enum CommitMsg {
+    Err(AppError),
+    Alert(FetchOther),
+    Major(bool),
+    Message(String),
+    Drop(Vec<File>),
+    Release(Option<File>),
+    Source(Option<File>),
+    Supplements(Vec<File>),
+    Describe(usize, String),
+    Remove(usize),
+    Checked(Part, File, Result<Rc<str>, FileError>),
+    Submit,
+    Pending(PendingCommit),
+    Progress(Part, u64),
+    Cancel,
+    Discard,
+    Committed(ObjectId),
+}
+
+#[derive(PartialEq, Properties)]
+pub(super) struct CommitProps {
+    pub(super) cfg: Rc<AppConfig>,
+    pub(super) err: Callback<AppError>,
+    pub(super) alert: Callback<FetchOther>,
+    pub(super) id: ObjectId,
+    pub(super) latest: Option<Rc<Version>>,
+    pub(super) committed: Callback<ObjectId>,
+}
+
+async fn create_session(cfg: &AppConfig, (file, sha256): (&File, Rc<str>)) -> Result<UploadSession, CommitMsg> {
+    match UploadSession::try_create(cfg, file, sha256).await {
+        Ok(FetchRes::Body(session)) => Ok(session),
+        Ok(FetchRes::Other(other)) => Err(CommitMsg::Alert(other)),
+        Err(e) => Err(CommitMsg::Err(e)),
+    }
+}
+
+async fn create_sessions(
+    cfg: &AppConfig,
+    stale: Option<PendingCommit>,
+    release: (&File, Rc<str>),
+    source: Option<(&File, Rc<str>)>,
+    supplements: Vec<(&File, Rc<str>)>,
+) -> Result<(UploadSession, Option<UploadSession>, Vec<UploadSession>), CommitMsg> {
+    if let Some(stale) = stale {
+        for session in stale.sessions() {
+            let _ = session.try_delete(cfg).await;
+        }
+    }
+    let release = create_session(cfg, release).await?;
+    let source = match source {
+        Some(source) => Some(create_session(cfg, source).await?),
+        None => None,
+    };
+    let mut sessions = Vec::new();
+    for supplement in supplements {
+        sessions.push(create_session(cfg, supplement).await?);
+    }
+    Ok((release, source, sessions))
+}
+
+async fn is_alive(cfg: &AppConfig, pending: &PendingCommit) -> AppResult<bool> {
+    for session in pending.sessions() {
+        if let FetchRes::Other(_) = session.try_offset(cfg).await? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+impl Commit {
+    fn check(ctx: &Context<Self>, part: Part, file: File) {
+        ctx.link().send_future({
+            let limits = ctx.props().cfg.limits;
+            async move {
+                let checked = match part {
+                    Part::Release => validation::check_release(&file, limits.release).await,
+                    Part::Source => validation::check_source(&file, limits.source).await,
+                    Part::Supplement(_) => validation::check_supplement(&file, limits.supplement),
+                };
+                let digest = match checked {
+                    Ok(()) => digest::sha256(&file).await.map(Rc::from).map_err(|e| FileError::Unreadable {
+                        name: file.name(),
+                        reason: e.to_string(),
+                    }),
+                    Err(e) => Err(e),
+                };
+                CommitMsg::Checked(part, file, digest)
+            }
+        });
+    }
+
+    fn is_ready(&self) -> bool {
+        self.release.is_some()
+            && matches!(self.release_check, Some(Ok(_)))
+            && (self.source.is_none() || matches!(self.source_check, Some(Ok(_))))
+            && self
+                .supplements
+                .iter()
+                .all(|s| matches!(s.check, Some(Ok(_))) && !s.description.trim().is_empty())
+    }
+
+    fn next(&self) -> (i32, i32) {
+        match self.latest {
+            Some(ref latest) if self.major => (latest.major_num + 1, 0),
+            Some(ref latest) => (latest.major_num, latest.minor_num + 1),
+            None => (1, 0),
+        }
+    }
+
+    fn effect(&self) -> String {
+        let latest = match self.latest {
+            Some(ref latest) => latest,
+            None => return "This is the first version; it will wait for an editor to assign reviewers.".to_owned(),
+        };
+        let current = format!("{}.{}", latest.major_num, latest.minor_num);
+        match (self.major, &latest.state) {
+            (true, _) => format!(
+                "A major revision starts a new round of review: it waits for an editor to assign reviewers, and version {current} becomes history."
+            ),
+            (false, VersionState::Passed(true)) => format!(
+                "A minor correction keeps the passed status of version {current} and is published without another review."
+            ),
+            (false, VersionState::Passed(false)) => format!(
+                "Version {current} was rejected; a minor correction does not reopen the review. Commit a major revision to be reviewed again."
+            ),
+            (false, VersionState::Uploaded | VersionState::Reviewing) => format!(
+                "A minor correction replaces version {current} in the ongoing review; assigned reviewers stay on it and see the corrected files."
+            ),
+            (false, VersionState::History) => format!(
+                "A minor correction keeps the review state of version {current}."
+            ),
+        }
+    }
+}
+
+impl Component for Commit {
+    type Message = CommitMsg;
+    type Properties = CommitProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let pending = PendingCommit::load(ctx.props().id);
+        Self {
+            latest: ctx.props().latest.clone(),
+            major: pending.as_ref().map(|p| p.major).unwrap_or(true),
+            message: pending.as_ref().map(|p| p.message.to_string().into()).unwrap_or_default(),
+            pending,
+            ..Default::default()
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        self.latest = ctx.props().latest.clone();
+        true
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            CommitMsg::Err(e) => {
+                self.uploading = None;
+                ctx.props().err.emit(e);
+                true
+            }
+            CommitMsg::Alert(other) => {
+                self.uploading = None;
+                ctx.props().alert.emit(other);
+                true
+            }
+            CommitMsg::Major(major) => {
+                self.major = major;
+                true
+            }
+            CommitMsg::Message(message) => {
+                self.message = message.into();
+                false
+            }
+            CommitMsg::Drop(files) => {
+                let mut supplements = Vec::new();
+                for file in files {
+                    match drop::classify(&file) {
+                        Some(Part::Release) => ctx.link().send_message(CommitMsg::Release(Some(file))),
+                        Some(Part::Source) => ctx.link().send_message(CommitMsg::Source(Some(file))),
+                        _ => supplements.push(file),
+                    }
+                }
+                if !supplements.is_empty() {
+                    ctx.link().send_message(CommitMsg::Supplements(supplements));
+                }
+                false
+            }
+            CommitMsg::Release(file) => {
+                if let Some(ref file) = file {
+                    Self::check(ctx, Part::Release, file.clone());
+                }
+                self.thumbnail = file
+                    .clone()
+                    .map(|file| gloo::file::ObjectUrl::from(gloo::file::Blob::from(file)));
+                self.release = file;
+                self.release_check = None;
+                true
+            }
+            CommitMsg::Source(file) => {
+                if let Some(ref file) = file {
+                    Self::check(ctx, Part::Source, file.clone());
+                }
+                self.source = file;
+                self.source_check = None;
+                true
+            }
+            CommitMsg::Supplements(files) => {
+                for file in files {
+                    Self::check(ctx, Part::Supplement(self.supplements.len()), file.clone());
+                    self.supplements.push(Supplement {
+                        file,
+                        description: AttrValue::default(),
+                        check: None,
+                    });
+                }
+                true
+            }
+            CommitMsg::Describe(i, description) => {
+                if let Some(supplement) = self.supplements.get_mut(i) {
+                    supplement.description = description.into();
+                }
+                true
+            }
+            CommitMsg::Remove(i) => {
+                if i < self.supplements.len() {
+                    self.supplements.remove(i);
+                }
+                true
+            }
+            CommitMsg::Checked(part, file, checked) => {
+                let (current, check) = match part {
+                    Part::Release => (self.release.as_ref(), &mut self.release_check),
+                    Part::Source => (self.source.as_ref(), &mut self.source_check),
+                    Part::Supplement(_) => match self.supplements.iter_mut().find(|s| s.file == file) {
+                        Some(supplement) => (Some(&supplement.file), &mut supplement.check),
+                        None => return false,
+                    },
+                };
+                if current != Some(&file) {
+                    return false;
+                }
+                *check = Some(checked);
+                true
+            }
+            CommitMsg::Submit => {
+                let release = match (&self.release, &self.release_check) {
+                    (Some(release), Some(Ok(digest))) if self.is_ready() => (release.clone(), digest.clone()),
+                    _ => return false,
+                };
+                let source = match (&self.source, &self.source_check) {
+                    (Some(source), Some(Ok(digest))) => Some((source.clone(), digest.clone())),
+                    _ => None,
+                };
+                let supplements = self
+                    .supplements
+                    .iter()
+                    .filter_map(|s| match s.check {
+                        Some(Ok(ref digest)) => Some((s.file.clone(), digest.clone(), Rc::<str>::from(s.description.trim()))),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                let abort = match AbortController::new() {
+                    Ok(abort) => abort,
+                    Err(e) => {
+                        ctx.props().err.emit(e.into());
+                        return false;
+                    }
+                };
+                let signal = abort.signal();
+                self.uploading = Some(Uploading {
+                    abort,
+                    release: 0,
+                    source: 0,
+                    supplements: vec![0; supplements.len()],
+                });
+                ctx.link().send_future({
+                    let cfg = ctx.props().cfg.clone();
+                    let id = ctx.props().id;
+                    let message: Rc<str> = self.message.as_str().into();
+                    let major = self.major;
+                    let stale = self.pending.clone();
+                    let on_pending = ctx.link().callback(CommitMsg::Pending);
+                    let progress = |part: Part| ctx.link().callback(move |n| CommitMsg::Progress(part, n));
+                    let (release_progress, source_progress) = (progress(Part::Release), progress(Part::Source));
+                    let supplement_progress = (0..supplements.len())
+                        .map(|i| progress(Part::Supplement(i)))
+                        .collect::<Vec<_>>();
+                    async move {
+                        let resumable = stale.as_ref().is_some_and(|p| {
+                            p.release.matches(&release.0, &release.1)
+                                && match (&p.source, &source) {
+                                    (Some(session), Some((file, digest))) => session.matches(file, digest),
+                                    (None, None) => true,
+                                    _ => false,
+                                }
+                                && p.supplements.len() == supplements.len()
+                                && p.supplements
+                                    .iter()
+                                    .zip(&supplements)
+                                    .all(|(pending, (file, digest, _))| pending.session.matches(file, digest))
+                        });
+                        let resumable = match stale {
+                            Some(ref stale) if resumable => match is_alive(&cfg, stale).await {
+                                Ok(alive) => alive,
+                                Err(e) => return CommitMsg::Err(e),
+                            },
+                            _ => false,
+                        };
+                        let describe = |sessions: Vec<UploadSession>| {
+                            sessions
+                                .into_iter()
+                                .zip(&supplements)
+                                .map(|(session, (_, _, description))| PendingSupplement {
+                                    session,
+                                    description: description.clone(),
+                                })
+                                .collect::<Vec<_>>()
+                        };
+                        let pending = match stale {
+                            Some(stale) if resumable => PendingCommit {
+                                message,
+                                major,
+                                supplements: describe(stale.supplements.iter().map(|s| s.session.clone()).collect()),
+                                ..stale
+                            },
+                            stale => {
+                                if stale.is_some() {
+                                    PendingCommit::clear(id);
+                                }
+                                match create_sessions(
+                                    &cfg,
+                                    stale,
+                                    (&release.0, release.1.clone()),
+                                    source.as_ref().map(|(file, digest)| (file, digest.clone())),
+                                    supplements.iter().map(|(file, digest, _)| (file, digest.clone())).collect(),
+                                )
+                                .await {
+                                    Ok((release, source, sessions)) => PendingCommit {
+                                        message,
+                                        major,
+                                        release,
+                                        source,
+                                        supplements: describe(sessions),
+                                    },
+                                    Err(msg) => return msg,
+                                }
+                            }
+                        };
+                        pending.store(id);
+                        on_pending.emit(pending.clone());
+
+                        let mut parts = vec![(&pending.release, &release.0, release_progress)];
+                        if let (Some(session), Some((file, _))) = (&pending.source, &source) {
+                            parts.push((session, file, source_progress));
+                        }
+                        for ((pending, (file, _, _)), progress) in
+                            pending.supplements.iter().zip(&supplements).zip(supplement_progress)
+                        {
+                            parts.push((&pending.session, file, progress));
+                        }
+                        for (session, file, progress) in parts {
+                            match upload::upload(&cfg, session, file, &signal, progress).await {
+                                Ok(None) => {}
+                                _ if signal.aborted() => return CommitMsg::Discard,
+                                Ok(Some(other)) => return CommitMsg::Alert(other),
+                                Err(e) => return CommitMsg::Err(e),
+                            }
+                        }
+                        if signal.aborted() {
+                            return CommitMsg::Discard;
+                        }
+                        match pending.try_commit(&cfg, id).await {
+                            Ok(FetchRes::Body(version_id)) => {
+                                PendingCommit::clear(id);
+                                CommitMsg::Committed(version_id)
+                            }
+                            Ok(FetchRes::Other(other)) => CommitMsg::Alert(other),
+                            Err(e) => CommitMsg::Err(e),
+                        }
+                    }
+                });
+                true
+            }
+            CommitMsg::Pending(pending) => {
+                self.pending = Some(pending);
+                true
+            }
+            CommitMsg::Progress(part, uploaded) => {
+                if let Some(ref mut uploading) = self.uploading {
+                    match part {
+                        Part::Release => uploading.release = uploaded,
+                        Part::Source => uploading.source = uploaded,
+                        Part::Supplement(i) => {
+                            if let Some(supplement) = uploading.supplements.get_mut(i) {
+                                *supplement = uploaded;
+                            }
+                        }
+                    }
+                }
+                true
+            }
+            CommitMsg::Cancel => {
+                if let Some(ref uploading) = self.uploading {
+                    uploading.abort.abort();
+                }
+                false
+            }
+            CommitMsg::Discard => {
+                self.uploading = None;
+                if let Some(pending) = self.pending.take() {
+                    PendingCommit::clear(ctx.props().id);
+                    let cfg = ctx.props().cfg.clone();
+                    wasm_bindgen_futures::spawn_local(async move {
+                        for session in pending.sessions() {
+                            let _ = session.try_delete(&cfg).await;
+                        }
+                    });
+                }
+                true
+            }
+            CommitMsg::Committed(id) => {
+                self.uploading = None;
+                self.pending = None;
+                ctx.props().committed.emit(id);
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let onsubmit = ctx.link().callback(|e: SubmitEvent| {
+            e.prevent_default();
+            CommitMsg::Submit
+        });
+        let choose = |major: bool| {
+            ctx.link().batch_callback(move |e: InputEvent| {
+                e.target_dyn_into::<HtmlInputElement>()
+                    .filter(|i| i.checked())
+                    .map(|_| CommitMsg::Major(major))
+            })
+        };
+        let input_message = ctx.link().batch_callback(|e: InputEvent| {
+            e.target_dyn_into::<HtmlTextAreaElement>()
+                .map(|a| CommitMsg::Message(a.value()))
+        });
+        let pick = |msg: fn(Option<File>) -> CommitMsg| {
+            ctx.link().batch_callback(move |e: Event| {
+                e.target_dyn_into::<HtmlInputElement>()
+                    .map(|i| msg(i.files().and_then(|f| f.get(0)).map(File::from)))
+            })
+        };
+        let view_check = |file: &Option<File>, check: &Option<Result<Rc<str>, FileError>>| match (file, check) {
+            (None, _) => html!(),
+            (Some(_), None) => html!({ " Checking..." }),
+            (Some(_), Some(Ok(digest))) => html!(<>{ " ✅ " }<small>{ "SHA-256: " }<code>{ &**digest }</code></small></>),
+            (Some(_), Some(Err(e))) => html!(<>{ " ❎ " }<small>{ e.to_string() }</small></>),
+        };
+        let ondragover = |e: DragEvent| e.prevent_default();
+        let ondrop = ctx.link().callback(|e: DragEvent| {
+            e.prevent_default();
+            let files = e.data_transfer().and_then(|d| d.files());
+            CommitMsg::Drop(
+                (0..files.as_ref().map_or(0, |f| f.length()))
+                    .filter_map(|i| files.as_ref().and_then(|f| f.get(i)))
+                    .map(File::from)
+                    .collect(),
+            )
+        });
+        let add_supplements = ctx.link().batch_callback(|e: Event| {
+            let input = e.target_dyn_into::<HtmlInputElement>()?;
+            let files = input.files()?;
+            let files = (0..files.length()).filter_map(|i| files.get(i)).map(File::from).collect();
+            input.set_value("");
+            Some(CommitMsg::Supplements(files))
+        });
+        let supplements = self.supplements.iter().enumerate().map(|(i, supplement)| {
+            let describe = ctx.link().batch_callback(move |e: InputEvent| {
+                e.target_dyn_into::<HtmlInputElement>()
+                    .map(|i| i.value())
+                    .map(|description| CommitMsg::Describe(i, description))
+            });
+            let remove = ctx.link().callback(move |_| CommitMsg::Remove(i));
+            let check = match supplement.check {
+                None => html!({ " Checking..." }),
+                Some(Ok(_)) => html!({ " ✅" }),
+                Some(Err(ref e)) => html!(<>{ " ❎ " }<small>{ e.to_string() }</small></>),
+            };
+            html_nested! {
+                <li>
+                    { "📎 " }{ supplement.file.name() }{ " (" }{ sizes::format(supplement.file.size()) }{ ")" }
+                    { check }
+                    <button type="button" onclick={ remove }>{ "Remove" }</button><br />
+                    <label>
+                        { "Description: " }
+                        <input type="text" required={ true } value={ supplement.description.clone() } oninput={ describe } />
+                    </label>
+                </li>
+            }
+        });
+        let (major_num, minor_num) = self.next();
+        let uploading = self.uploading.is_some();
+        let progress = match (&self.uploading, &self.pending) {
+            (Some(uploading), Some(pending)) => html! {
+                <>
+                    { upload::view_progress("Release file", &pending.release, uploading.release) }
+                    if let Some(ref source) = pending.source {
+                        { upload::view_progress("Source file", source, uploading.source) }
+                    }
+                    { for pending.supplements.iter().zip(&uploading.supplements).map(|(supplement, uploaded)| {
+                        upload::view_progress("Supplementary file", &supplement.session, *uploaded)
+                    }) }
+                </>
+            },
+            (Some(_), None) => html!(<p>{ "Preparing upload..." }</p>),
+            _ => html!(),
+        };
+        let resume = match (&self.uploading, &self.pending) {
+            (None, Some(pending)) => {
+                let files = pending
+                    .sessions()
+                    .map(|s| s.name.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                html! {
+                    <p>
+                        { format!("An upload of {files} was interrupted. Select the same files and commit again to resume it, or ") }
+                        <button type="button" onclick={ ctx.link().callback(|_| CommitMsg::Discard) }>{ "discard it" }</button>
+                        { "." }
+                    </p>
+                }
+            }
+            _ => html!(),
+        };
+        html! {
+            <form {onsubmit}><fieldset disabled={ uploading }>
+                <legend>{ "Commit" }</legend>
+                { resume }
+                <p>
+                    <label>
+                        <input type="radio" name="revision" value="major" checked={ self.major } oninput={ choose(true) } />
+                        { "Major revision" }
+                    </label>
+                    <label>
+                        <input type="radio" name="revision" value="minor" checked={ !self.major } oninput={ choose(false) }
+                            disabled={ self.latest.is_none() } />
+                        { "Minor correction" }
+                    </label>
+                </p>
+
+                <p>
+                    { "New version: " }<strong>{ major_num }{ "." }{ minor_num }</strong><br />
+                    <small>{ self.effect() }</small>
+                </p>
+
+                <p><label>
+                    { "Commit Message: "}
+                    <textarea type="text" name="message" required={ true } oninput={ input_message } value={ self.message.clone() } />
+                </label></p>
+
+                <fieldset {ondragover} {ondrop}>
+                    <legend>{ "Files" }</legend>
+                    <p>
+                        { "Drop the release PDF, the source archive and any supplementary materials here, or choose them below. " }
+                        { "Files that are neither a PDF nor a source archive are added as supplementary materials." }
+                    </p>
+                    if let Some(ref release) = self.release {
+                        { drop::view_file(release, self.thumbnail.as_ref()) }
+                    }
+                    if let Some(ref source) = self.source {
+                        { drop::view_file(source, None) }
+                    }
+                </fieldset>
+
+                <p><label>
+                    { "Release file: " }
+                    <input type="file" name="release" accept={ mime::APPLICATION_PDF.to_string() } required={ self.release.is_none() }
+                        onchange={ pick(CommitMsg::Release) } />
+                    { view_check(&self.release, &self.release_check) }
+                </label></p>
+
+                <p><label>
+                    { "Source file: " }
+                    <input type="file" name="source" accept={ validation::source_accept() }
+                    required={ false } onchange={ pick(CommitMsg::Source) } />
+                    { view_check(&self.source, &self.source_check) }
+                </label></p>
+
+                <fieldset>
+                    <legend>{ "Supplementary materials" }</legend>
+                    <p><small>
+                        { format!("Datasets, code, appendices or media, up to {} each. Describe every file in a few words.",
+                            sizes::format(ctx.props().cfg.limits.supplement)) }
+                    </small></p>
+                    if !self.supplements.is_empty() {
+                        <ul>{ for supplements }</ul>
+                    }
+                    <p><label>
+                        { "Add files: " }
+                        <input type="file" name="supplements" multiple={ true } onchange={ add_supplements } />
+                    </label></p>
+                </fieldset>
+
+                <p>
+                    <input type="submit" value="Commit" disabled={ !self.is_ready() } />
+                </p>
+            </fieldset>
+            { progress }
+            if uploading {
+                <p>
+                    <button type="button" onclick={ ctx.link().callback(|_| CommitMsg::Cancel) }>{ "Cancel upload" }</button>
+                </p>
+            }
+            </form>
+        }
+    }
+}
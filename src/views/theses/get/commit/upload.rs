@@ -0,0 +1,47 @@
+use gloo::file::File;
+use web_sys::AbortSignal;
+use yew::{html, Callback, Html};
+
+use crate::models::{
+    common::{AppConfig, AppResult, FetchOther, FetchRes},
+    upload::{UploadSession, CHUNK_SIZE},
+};
+
+pub(super) async fn upload(
+    cfg: &AppConfig,
+    session: &UploadSession,
+    file: &File,
+    signal: &AbortSignal,
+    progress: Callback<u64>,
+) -> AppResult<Option<FetchOther>> {
+    let mut offset = match session.try_offset(cfg).await? {
+        FetchRes::Body(offset) => offset,
+        FetchRes::Other(other) => return Ok(Some(other)),
+    };
+    progress.emit(offset);
+    while offset < session.size {
+        let end = (offset + CHUNK_SIZE).min(session.size);
+        if let Some(other) = session.try_put_chunk(cfg, file, offset, end, signal).await? {
+            return Ok(Some(other));
+        }
+        offset = end;
+        progress.emit(offset);
+    }
+    Ok(None)
+}
+
+pub(super) fn view_progress(label: &str, session: &UploadSession, uploaded: u64) -> Html {
+    let percent = match session.size {
+        0 => 100,
+        size => uploaded * 100 / size,
+    };
+    html! {
+        <p>
+            <label>
+                { label }{ ": " }{ &*session.name }<br />
+                <progress value={ uploaded.to_string() } max={ session.size.to_string() } />
+                { format!(" {percent}%") }
+            </label>
+        </p>
+    }
+}
@@ -0,0 +1,82 @@
+use std::rc::Rc;
+
+use yew::{function_component, html, Callback, Html, Properties};
+
+use crate::models::{
+    common::{AppConfig, AppError, FetchOther, FetchRes},
+    doi::{self, DoiState},
+    thesis::Thesis,
+};
+
+#[derive(PartialEq, Properties)]
+pub(super) struct DoiProps {
+    pub(super) cfg: Rc<AppConfig>,
+    pub(super) err: Callback<AppError>,
+    pub(super) alert: Callback<FetchOther>,
+    pub(super) val: Rc<Thesis>,
+    pub(super) refresh: Callback<Rc<Thesis>>,
+    pub(super) can_request: bool,
+}
+
+#[function_component]
+pub(super) fn Doi(props: &DoiProps) -> Html {
+    let val = props.val.clone();
+
+    let status = match (val.doi.as_deref(), val.doi_request.as_ref()) {
+        (Some(d), _) => {
+            let href = doi::url(d);
+            html! {
+                <p>
+                    { "DOI: " }<a href={ href.clone() }>{ href }</a>
+                    if val.doi_request.as_ref().is_some_and(|r| r.state == DoiState::Registered) {
+                        { " (registered)" }
+                    }
+                </p>
+            }
+        }
+        (None, Some(request)) => html! {
+            <p>
+                { "DOI: " }
+                <mark>{ match request.state {
+                    DoiState::Pending => "Pending",
+                    DoiState::Registered => "Registered",
+                } }</mark>
+                { " since " }{ request.requested_at.format("%Y-%m-%d").to_string() }
+            </p>
+        },
+        (None, None) => html!(),
+    };
+    let request = (props.can_request && val.doi.is_none() && val.doi_request.is_none()).then(|| {
+        let onclick = Callback::from({
+            let cfg = props.cfg.clone();
+            let err = props.err.clone();
+            let alert = props.alert.clone();
+            let refresh = props.refresh.clone();
+            let id = val.id._id;
+            move |_| {
+                let cfg = cfg.clone();
+                let err = err.clone();
+                let alert = alert.clone();
+                let refresh = refresh.clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    match Thesis::try_request_doi(&cfg, id).await {
+                        Ok(FetchRes::Body(val)) => refresh.emit(val),
+                        Ok(FetchRes::Other(other)) => alert.emit(other),
+                        Err(e) => err.emit(e),
+                    }
+                });
+            }
+        });
+        html! {
+            <p>
+                <button {onclick}>{ "Request DOI" }</button>
+            </p>
+        }
+    });
+    html! {
+        <>
+            { status }
+            { for request }
+        </>
+    }
+}
@@ -0,0 +1,355 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Not,
+    rc::Rc,
+};
+
+use serde_json::{Map, Value};
+use web_sys::{HtmlInputElement, HtmlTextAreaElement, InputEvent};
+use yew::{html, AttrValue, Callback, Component, Context, Html, Properties, TargetCast};
+
+use crate::{
+    models::{
+        common::{AppConfig, AppError, FetchOther, FetchRes},
+        doi,
+        profile::PublicProfile,
+        thesis::{Thesis, Translation},
+    },
+    views::{markup, profile_list::ProfileList, theses::{keywords, languages, translations}},
+};
+
+use super::merge;
+
+type Changes = Map<String, Value>;
+
+#[derive(Default)]
+pub(super) struct Edit {
+    base: Rc<Thesis>,
+    conflict: Option<(Rc<Thesis>, Rc<Changes>)>,
+    title: AttrValue,
+    abstraction: AttrValue,
+    doi: AttrValue,
+    keywords: Rc<Vec<AttrValue>>,
+    authors: Option<Rc<Vec<Rc<PublicProfile>>>>,
+    authors_failed: bool,
+    languages: Rc<BTreeSet<Rc<str>>>,
+    translations: Rc<BTreeMap<Rc<str>, Translation>>,
+}
+
+pub(super) enum EditMsg {
+    Err(AppError),
+    Alert(FetchOther),
+    Loaded(Rc<Vec<Rc<PublicProfile>>>),
+    InputTitle(String),
+    InputAbstraction(String),
+    InputDoi(String),
+    UpdateKeywords(Rc<Vec<AttrValue>>),
+    UpdateAuthors(Rc<Vec<Rc<PublicProfile>>>),
+    UpdateLanguages(Rc<BTreeSet<Rc<str>>>),
+    UpdateTranslations(Rc<BTreeMap<Rc<str>, Translation>>),
+    Submit,
+    Conflict(Changes),
+    Theirs(Rc<Thesis>, Rc<Changes>),
+    Resolve(Changes),
+    Discard,
+    Submitted(Rc<Thesis>),
+}
+
+#[derive(PartialEq, Properties)]
+pub(super) struct EditProps {
+    pub(super) cfg: Rc<AppConfig>,
+    pub(super) err: Callback<AppError>,
+    pub(super) alert: Callback<FetchOther>,
+    pub(super) val: Rc<Thesis>,
+    pub(super) refresh: Callback<Rc<Thesis>>,
+    pub(super) cancel: Callback<()>,
+}
+
+impl Edit {
+    fn patch(ctx: &Context<Self>, base: Rc<Thesis>, changes: Changes) {
+        ctx.link().send_future({
+            let cfg = ctx.props().cfg.clone();
+            async move {
+                match Thesis::try_patch(&cfg, base.id._id, base.etag.as_deref(), &changes).await {
+                    Ok(FetchRes::Body(val)) => EditMsg::Submitted(val),
+                    Ok(FetchRes::Other(other)) if other.status == 412 => EditMsg::Conflict(changes),
+                    Ok(FetchRes::Other(other)) => EditMsg::Alert(other),
+                    Err(e) => EditMsg::Err(e),
+                }
+            }
+        });
+    }
+
+    fn changes(&self, val: &Thesis) -> Changes {
+        let mut changes = Map::new();
+        if *self.title != *val.title {
+            changes.insert("title".into(), self.title.as_str().into());
+        }
+        if *self.abstraction != *val.abstraction {
+            changes.insert("abstraction".into(), self.abstraction.as_str().into());
+        }
+        let doi = doi::normalize(&self.doi);
+        if doi.as_deref() != val.doi.as_deref() {
+            changes.insert("doi".into(), serde_json::json!(doi));
+        }
+        if !self.keywords.iter().map(|k| k.as_str()).eq(val.keywords.iter().map(|k| &**k)) {
+            changes.insert(
+                "keywords".into(),
+                self.keywords.iter().map(|k| k.as_str()).collect::<Vec<_>>().into(),
+            );
+        }
+        if let Some(ref authors) = self.authors {
+            if !authors.iter().map(|a| a._id).eq(val.author_ids.iter().copied()) {
+                changes.insert(
+                    "author_ids".into(),
+                    serde_json::json!(authors.iter().map(|a| a._id).collect::<Vec<_>>()),
+                );
+            }
+        }
+        if self.languages != val.languages {
+            changes.insert("languages".into(), serde_json::json!(self.languages));
+        }
+        let translations = translations::retain(&self.translations, &self.languages);
+        if translations != *val.translations {
+            changes.insert("translations".into(), serde_json::json!(translations));
+        }
+        changes
+    }
+}
+
+impl Component for Edit {
+    type Message = EditMsg;
+    type Properties = EditProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let val = ctx.props().val.clone();
+        ctx.link().send_future({
+            let cfg = ctx.props().cfg.clone();
+            let ids = val.author_ids.clone();
+            async move {
+                let mut authors = Vec::with_capacity(ids.len());
+                for id in ids.iter() {
+                    match PublicProfile::try_get(&cfg, *id).await {
+                        Ok(FetchRes::Body(author)) => authors.push(author),
+                        Ok(FetchRes::Other(other)) => return EditMsg::Alert(other),
+                        Err(e) => return EditMsg::Err(e),
+                    }
+                }
+                EditMsg::Loaded(Rc::new(authors))
+            }
+        });
+        Self {
+            base: val.clone(),
+            conflict: None,
+            title: val.title.to_string().into(),
+            abstraction: val.abstraction.to_string().into(),
+            doi: val.doi.as_deref().unwrap_or_default().to_owned().into(),
+            keywords: Rc::new(val.keywords.iter().map(|k| k.to_string().into()).collect()),
+            authors: None,
+            authors_failed: false,
+            languages: val.languages.clone(),
+            translations: val.translations.clone(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            EditMsg::Err(e) => {
+                ctx.props().err.emit(e);
+                self.authors_failed = self.authors.is_none();
+                self.authors_failed
+            }
+            EditMsg::Alert(other) => {
+                ctx.props().alert.emit(other);
+                self.authors_failed = self.authors.is_none();
+                self.authors_failed
+            }
+            EditMsg::Loaded(authors) => {
+                self.authors = Some(authors);
+                true
+            }
+            EditMsg::InputTitle(title) => {
+                self.title = title.into();
+                true
+            }
+            EditMsg::InputAbstraction(abstraction) => {
+                self.abstraction = abstraction.into();
+                true
+            }
+            EditMsg::InputDoi(doi) => {
+                self.doi = doi.into();
+                true
+            }
+            EditMsg::UpdateLanguages(languages) => {
+                self.languages = languages;
+                true
+            }
+            EditMsg::UpdateKeywords(keywords) => {
+                self.keywords = keywords;
+                false
+            }
+            EditMsg::UpdateAuthors(authors) => {
+                self.authors = Some(authors);
+                false
+            }
+            EditMsg::UpdateTranslations(translations) => {
+                self.translations = translations;
+                false
+            }
+            EditMsg::Submit => {
+                let changes = self.changes(&self.base);
+                if changes.is_empty() {
+                    ctx.props().cancel.emit(());
+                    return false;
+                }
+                Self::patch(ctx, self.base.clone(), changes);
+                false
+            }
+            EditMsg::Conflict(changes) => {
+                ctx.link().send_future({
+                    let cfg = ctx.props().cfg.clone();
+                    let id = self.base.id._id;
+                    async move {
+                        match Thesis::try_get(&cfg, id).await {
+                            Ok(FetchRes::Body(theirs)) => EditMsg::Theirs(theirs, Rc::new(changes)),
+                            Ok(FetchRes::Other(other)) => EditMsg::Alert(other),
+                            Err(e) => EditMsg::Err(e),
+                        }
+                    }
+                });
+                false
+            }
+            EditMsg::Theirs(theirs, changes) => {
+                self.conflict = Some((theirs, changes));
+                true
+            }
+            EditMsg::Resolve(changes) => {
+                if let Some((theirs, _)) = self.conflict.take() {
+                    self.base = theirs.clone();
+                    if changes.is_empty() {
+                        ctx.props().refresh.emit(theirs);
+                    } else {
+                        Self::patch(ctx, theirs, changes);
+                    }
+                }
+                true
+            }
+            EditMsg::Discard => {
+                if let Some((theirs, _)) = self.conflict.take() {
+                    ctx.props().refresh.emit(theirs);
+                }
+                false
+            }
+            EditMsg::Submitted(val) => {
+                ctx.props().refresh.emit(val);
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let authors = match self.authors {
+            Some(ref authors) => authors.clone(),
+            None if self.authors_failed => {
+                let onclick = ctx.props().cancel.reform(|_| ());
+                return html! {
+                    <p>
+                        { "The authors could not be loaded, so the metadata cannot be edited right now. " }
+                        <button {onclick}>{ "Close" }</button>
+                    </p>
+                };
+            }
+            None => return html!(<p>{ "Loading authors..." }</p>),
+        };
+        let check_title = ctx.link().batch_callback(|e: InputEvent| {
+            e.target_dyn_into::<HtmlInputElement>()
+                .map(|i| EditMsg::InputTitle(i.value()))
+        });
+        let check_abstraction = ctx.link().batch_callback(|e: InputEvent| {
+            e.target_dyn_into::<HtmlTextAreaElement>()
+                .map(|i| EditMsg::InputAbstraction(i.value()))
+        });
+        let check_doi = ctx.link().batch_callback(|e: InputEvent| {
+            e.target_dyn_into::<HtmlInputElement>()
+                .map(|i| EditMsg::InputDoi(i.value()))
+        });
+        let valid_doi = self.doi.trim().is_empty() || doi::normalize(&self.doi).is_some();
+        let err = ctx.link().callback(EditMsg::Err);
+        let alert = ctx.link().callback(EditMsg::Alert);
+        let update_keywords = ctx.link().callback(EditMsg::UpdateKeywords);
+        let update_authors = ctx.link().callback(EditMsg::UpdateAuthors);
+        let update_languages = ctx.link().callback(EditMsg::UpdateLanguages);
+        let update_translations = ctx.link().callback(EditMsg::UpdateTranslations);
+        let submit = ctx.link().callback(|_| EditMsg::Submit);
+        let cancel = ctx.props().cancel.reform(|_| ());
+        let merge = self.conflict.as_ref().map(|(theirs, changes)| {
+            let resolve = ctx.link().callback(EditMsg::Resolve);
+            let discard = ctx.link().callback(|_| EditMsg::Discard);
+            html! {
+                <merge::Merge
+                    base={ self.base.clone() }
+                    theirs={ theirs.clone() }
+                    changes={ changes.clone() }
+                    {resolve}
+                    cancel={ discard }
+                />
+            }
+        });
+        html! {
+            <fieldset>
+                <legend>{ "Edit Metadata" }</legend>
+                <p>
+                    <label>
+                        { "Title: " }
+                        <input type="text" oninput={ check_title } value={ self.title.clone() } />
+                        { if self.title.is_empty().not() { " ✅" } else { " ❎" }}
+                    </label>
+                </p>
+
+                <div>
+                    <label>
+                        { "Abstraction: " }<br />
+                        <textarea type="text" oninput={ check_abstraction } value={ self.abstraction.clone() } />
+                        { if self.abstraction.len() >= 140 { " ✅" } else { " ❎" }}
+                    </label>
+                    { markup::preview(&self.abstraction) }
+                </div>
+
+                <p>
+                    <label>
+                        { "DOI: " }
+                        <input type="text" placeholder="10.1000/xyz123" oninput={ check_doi } value={ self.doi.clone() } />
+                        { if valid_doi { " ✅" } else { " ❎" }}
+                    </label>
+                </p>
+
+                <p>
+                    <ProfileList cfg={ ctx.props().cfg.clone() } {err} {alert} vals={ update_authors } init={ authors } />
+                </p>
+
+                <p>
+                    <keywords::Keywords vals={ update_keywords } init={ self.keywords.clone() } />
+                </p>
+
+                <p>
+                    <languages::Languages vals={ update_languages } init={ self.languages.clone() } />
+                </p>
+
+                <p>
+                    <translations::Translations
+                        languages={ self.languages.clone() }
+                        vals={ update_translations }
+                        init={ self.translations.clone() }
+                    />
+                </p>
+
+                <p>
+                    <button onclick={ submit } disabled={ self.title.is_empty() || self.abstraction.len() < 140 || self.languages.is_empty() || !valid_doi }>{ "Save" }</button>
+                    <button onclick={ cancel }>{ "Cancel" }</button>
+                </p>
+
+                { for merge }
+            </fieldset>
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use std::{collections::BTreeMap, rc::Rc};
+
+use serde_json::{Map, Value};
+use yew::{html, html_nested, Callback, Component, Context, Html, Properties};
+
+use crate::models::thesis::Thesis;
+
+const FIELDS: [(&str, &str); 7] = [
+    ("title", "Title"),
+    ("abstraction", "Abstraction"),
+    ("doi", "DOI"),
+    ("keywords", "Key Words"),
+    ("author_ids", "Authors"),
+    ("languages", "Languages"),
+    ("translations", "Translations"),
+];
+
+fn fields(val: &Thesis) -> Map<String, Value> {
+    let json = serde_json::to_value(val).unwrap_or_default();
+    FIELDS
+        .iter()
+        .filter_map(|(k, _)| json.get(k).map(|v| (k.to_string(), v.clone())))
+        .collect()
+}
+
+fn display(val: Option<&Value>) -> String {
+    match val {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Array(vals)) => vals
+            .iter()
+            .map(|v| match v {
+                Value::String(s) => s.clone(),
+                Value::Object(o) => o.get("$oid").and_then(Value::as_str).unwrap_or_default().to_owned(),
+                v => v.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        Some(Value::Object(translations)) => translations
+            .iter()
+            .map(|(l, t)| format!("{}: {}", l, t.get("title").and_then(Value::as_str).unwrap_or_default()))
+            .collect::<Vec<_>>()
+            .join("; "),
+        Some(Value::Null) | None => String::new(),
+        Some(v) => v.to_string(),
+    }
+}
+
+#[derive(PartialEq, Clone, Copy)]
+pub(super) enum Side {
+    Mine,
+    Theirs,
+}
+
+enum Outcome {
+    Unchanged,
+    Auto(Side),
+    Conflict,
+}
+
+#[derive(Default)]
+pub(super) struct Merge {
+    choices: BTreeMap<&'static str, Side>,
+}
+
+pub(super) enum MergeMsg {
+    Choose(&'static str, Side),
+    Resolve,
+}
+
+#[derive(PartialEq, Properties)]
+pub(super) struct MergeProps {
+    pub(super) base: Rc<Thesis>,
+    pub(super) theirs: Rc<Thesis>,
+    pub(super) changes: Rc<Map<String, Value>>,
+    pub(super) resolve: Callback<Map<String, Value>>,
+    pub(super) cancel: Callback<()>,
+}
+
+impl Merge {
+    fn sides(ctx: &Context<Self>) -> (Map<String, Value>, Map<String, Value>, Map<String, Value>) {
+        let base = fields(&ctx.props().base);
+        let mut mine = base.clone();
+        mine.extend(ctx.props().changes.iter().map(|(k, v)| (k.clone(), v.clone())));
+        (base, mine, fields(&ctx.props().theirs))
+    }
+
+    fn outcome(key: &str, base: &Map<String, Value>, mine: &Map<String, Value>, theirs: &Map<String, Value>) -> Outcome {
+        let (base, mine, theirs) = (base.get(key), mine.get(key), theirs.get(key));
+        if mine == theirs {
+            Outcome::Unchanged
+        } else if mine == base {
+            Outcome::Auto(Side::Theirs)
+        } else if theirs == base {
+            Outcome::Auto(Side::Mine)
+        } else {
+            Outcome::Conflict
+        }
+    }
+}
+
+impl Component for Merge {
+    type Message = MergeMsg;
+    type Properties = MergeProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self::default()
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            MergeMsg::Choose(key, side) => {
+                self.choices.insert(key, side);
+                true
+            }
+            MergeMsg::Resolve => {
+                let (base, mine, theirs) = Self::sides(ctx);
+                let changes = FIELDS
+                    .iter()
+                    .filter_map(|(key, _)| {
+                        let side = match Self::outcome(key, &base, &mine, &theirs) {
+                            Outcome::Unchanged => return None,
+                            Outcome::Auto(side) => side,
+                            Outcome::Conflict => self.choices.get(key).copied()?,
+                        };
+                        match side {
+                            Side::Mine => mine.get(*key).map(|v| (key.to_string(), v.clone())),
+                            Side::Theirs => None,
+                        }
+                    })
+                    .collect();
+                ctx.props().resolve.emit(changes);
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let (base, mine, theirs) = Self::sides(ctx);
+        let mut resolved = true;
+        let rows = FIELDS
+            .iter()
+            .map(|(key, label)| {
+                let key: &'static str = key;
+                let result = match Self::outcome(key, &base, &mine, &theirs) {
+                    Outcome::Unchanged => html!({ "Unchanged" }),
+                    Outcome::Auto(Side::Mine) => html!({ "Yours" }),
+                    Outcome::Auto(Side::Theirs) => html!({ "Theirs" }),
+                    Outcome::Conflict => {
+                        let choice = self.choices.get(key).copied();
+                        resolved &= choice.is_some();
+                        let mine = ctx.link().callback(move |_| MergeMsg::Choose(key, Side::Mine));
+                        let theirs = ctx.link().callback(move |_| MergeMsg::Choose(key, Side::Theirs));
+                        html! {
+                            <>
+                                <label>
+                                    <input type="radio" name={ key } onchange={ mine } checked={ choice == Some(Side::Mine) } />
+                                    { "Yours" }
+                                </label>
+                                <label>
+                                    <input type="radio" name={ key } onchange={ theirs } checked={ choice == Some(Side::Theirs) } />
+                                    { "Theirs" }
+                                </label>
+                            </>
+                        }
+                    }
+                };
+                html_nested! {
+                    <tr>
+                        <th>{ label }</th>
+                        <td>{ display(base.get(key)) }</td>
+                        <td>{ display(mine.get(key)) }</td>
+                        <td>{ display(theirs.get(key)) }</td>
+                        <td>{ result }</td>
+                    </tr>
+                }
+            })
+            .collect::<Vec<_>>();
+        let resolve = ctx.link().callback(|_| MergeMsg::Resolve);
+        let cancel = ctx.props().cancel.reform(|_| ());
+        html! {
+            <dialog open={ true }>
+                <p>{ "Someone else saved this thesis while you were editing. Choose which value to keep for each conflicting field." }</p>
+                <table>
+                    <thead>
+                        <tr>
+                            <th />
+                            <th>{ "Original" }</th>
+                            <th>{ "Yours" }</th>
+                            <th>{ "Theirs" }</th>
+                            <th>{ "Keep" }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for rows }
+                    </tbody>
+                </table>
+                <button onclick={ resolve } disabled={ !resolved }>{ "Save merged" }</button>
+                <button onclick={ cancel }>{ "Discard my changes" }</button>
+            </dialog>
+        }
+    }
+}
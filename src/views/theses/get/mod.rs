@@ -6,6 +6,16 @@ use crate::models::{common::{AppConfig, FetchRes}, thesis::Thesis};
 use super::super::alerts::AlertBox;
 
 pub mod page;
+pub mod timeline;
+mod changelog;
+mod cite;
+mod commit;
+mod doi;
+mod edit;
+mod merge;
+mod share;
+mod transfer;
+mod withdraw;
 
 #[derive(PartialEq, Properties)]
 pub struct GetProps {
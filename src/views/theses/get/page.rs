@@ -1,20 +1,34 @@
 use std::rc::Rc;
 
-use web_sys::{FormData, HtmlFormElement, RequestCredentials, SubmitEvent};
-use yew::{html, html_nested, Component, Context, Html, Properties, TargetCast};
+use bson::oid::ObjectId;
+use gloo::storage::{LocalStorage, Storage};
+use yew::{html, html_nested, Component, Context, Html, Properties, Suspense};
 use yew_router::scope_ext::RouterScopeExt;
 
 use crate::{
     models::{
         common::{AppConfig, AppError, FetchOther, FetchRes},
-        profile::PublicProfile,
+        profile::{Profile, PublicProfile},
         thesis::Thesis,
+        version::Version,
     },
-    views::{alerts::AlertBox, Route},
+    views::{alerts::AlertBox, library::bookmark::Bookmark, markup, theses::languages::display_name, Route},
 };
 
+use super::{
+    cite::Cite, commit::Commit, doi::Doi, edit, share::ShareThesis, timeline::Timeline, transfer::Transfer,
+    withdraw::Withdraw,
+};
+
+const LANGUAGE_KEY: &str = "thesis_language";
+
 #[derive(Default)]
 pub struct Page {
+    val: Rc<Thesis>,
+    versions: Option<Result<Rc<Vec<Rc<Version>>>, Html>>,
+    me: Option<ObjectId>,
+    editing: bool,
+    language: Option<Rc<str>>,
     err: Option<crate::models::common::AppError>,
     alert: Option<Rc<FetchOther>>,
 }
@@ -22,8 +36,13 @@ pub struct Page {
 pub enum PageMsg {
     Err(AppError),
     Alert(FetchOther),
-    File(FormData),
-    Version(bson::oid::ObjectId),
+    Me(Option<ObjectId>),
+    Edit(bool),
+    Language(Option<Rc<str>>),
+    Refresh(Rc<Thesis>),
+    Reload,
+    Versions(Result<Rc<Vec<Rc<Version>>>, Html>),
+    Version(ObjectId),
 }
 
 #[derive(PartialEq, Properties)]
@@ -32,12 +51,43 @@ pub struct PageProps {
     pub cfg: Rc<AppConfig>,
 }
 
+impl Page {
+    fn load_versions(ctx: &Context<Self>, id: ObjectId) {
+        ctx.link().send_future({
+            let cfg = ctx.props().cfg.clone();
+            async move {
+                match Version::try_list(&cfg, &[("thesis_id", &id.to_hex())]).await {
+                    Ok(FetchRes::Body(vals)) => PageMsg::Versions(Ok(vals)),
+                    Ok(FetchRes::Other(other)) => {
+                        PageMsg::Versions(Err(html!(<AlertBox refresh={ Some(Rc::new(other)) } />)))
+                    }
+                    Err(e) => PageMsg::Versions(Err(e.view())),
+                }
+            }
+        });
+    }
+}
+
 impl Component for Page {
     type Message = PageMsg;
     type Properties = PageProps;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        Self::default()
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_future({
+            let cfg = ctx.props().cfg.clone();
+            async move {
+                match Profile::try_get_self(&cfg).await {
+                    Ok(FetchRes::Body(me)) => PageMsg::Me(Some(me.public_profile._id)),
+                    _ => PageMsg::Me(None),
+                }
+            }
+        });
+        Self::load_versions(ctx, ctx.props().val.id._id);
+        Self {
+            val: ctx.props().val.clone(),
+            language: LocalStorage::get::<String>(LANGUAGE_KEY).ok().map(Rc::from),
+            ..Default::default()
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -50,36 +100,48 @@ impl Component for Page {
                 self.alert = Some(Rc::new(other));
                 true
             }
-            PageMsg::File(data) => {
+            PageMsg::Me(me) => {
+                self.me = me;
+                true
+            }
+            PageMsg::Edit(editing) => {
+                self.editing = editing;
+                true
+            }
+            PageMsg::Language(language) => {
+                match language {
+                    Some(ref language) => {
+                        if let Err(e) = LocalStorage::set(LANGUAGE_KEY, &**language) {
+                            gloo::console::error!(e.to_string());
+                        }
+                    }
+                    None => LocalStorage::delete(LANGUAGE_KEY),
+                }
+                self.language = language;
+                true
+            }
+            PageMsg::Refresh(val) => {
+                Self::load_versions(ctx, val.id._id);
+                self.val = val;
+                self.editing = false;
+                true
+            }
+            PageMsg::Reload => {
                 ctx.link().send_future({
                     let cfg = ctx.props().cfg.clone();
-                    let id = ctx.props().val.id._id;
+                    let id = self.val.id._id;
                     async move {
-                        let url = match cfg.api.join(&format!("theses/{}/commit", id.to_hex())) {
-                            Ok(url) => url,
-                            Err(e) => {
-                                return PageMsg::Err(e.into());
-                            }
-                        };
-                        let req = match gloo::net::http::Request::post(url.as_str())
-                            .credentials(RequestCredentials::Include)
-                            .body(&data)
-                        {
-                            Ok(req) => req,
-                            Err(e) => {
-                                return PageMsg::Err(e.into());
-                            }
-                        };
-                        let id = match FetchRes::try_from_gloo_res(req.send().await).await {
-                            Ok(res) => match res {
-                                FetchRes::Body(body) => body,
-                                FetchRes::Other(other) => return PageMsg::Alert(other),
-                            },
-                            Err(e) => return PageMsg::Err(e.into()),
-                        };
-                        PageMsg::Version(id)
+                        match Thesis::try_get(&cfg, id).await {
+                            Ok(FetchRes::Body(val)) => PageMsg::Refresh(val),
+                            Ok(FetchRes::Other(other)) => PageMsg::Alert(other),
+                            Err(e) => PageMsg::Err(e),
+                        }
                     }
                 });
+                false
+            }
+            PageMsg::Versions(vals) => {
+                self.versions = Some(vals);
                 true
             }
             PageMsg::Version(id) => {
@@ -96,16 +158,17 @@ impl Component for Page {
             return err.view();
         }
         let cfg = ctx.props().cfg.to_owned();
-        let val = ctx.props().val.clone();
-        let file_upload = ctx.link().batch_callback(move |e: SubmitEvent| {
-            e.prevent_default();
-            e.target_dyn_into::<HtmlFormElement>().map(|form| {
-                match FormData::new_with_form(&form) {
-                    Ok(data) => PageMsg::File(data),
-                    Err(e) => PageMsg::Err(e.into()),
-                }
-            })
-        });
+        let val = self.val.clone();
+        let withdrawn = val.withdrawal.is_some();
+        let is_owner = self.me == Some(val.id.owner_id);
+        let is_author = self.me.is_some_and(|me| val.author_ids.contains(&me));
+        let can_edit = !withdrawn && (is_owner || is_author);
+        let can_withdraw = !withdrawn && (is_owner || val.is_editor);
+        let can_request_doi = !withdrawn && val.id.is_passed && val.is_editor;
+        let latest = match self.versions {
+            Some(Ok(ref vals)) => Version::latest(vals).cloned(),
+            _ => None,
+        };
 
         let authors = val.author_ids.iter().map(|id| {
             html_nested! {
@@ -118,47 +181,151 @@ impl Component for Page {
             .keywords
             .iter()
             .map(|k| html_nested!(<b>{ k }{ "&nbsp;" }</b>));
+        let language = self
+            .language
+            .as_deref()
+            .filter(|l| val.translations.contains_key(*l));
+        let switch = val.translations.keys().map(|l| {
+            let onclick = ctx.link().callback({
+                let l = l.clone();
+                move |_| PageMsg::Language(Some(l.clone()))
+            });
+            html_nested!(<button {onclick} disabled={ language == Some(&**l) }>{ display_name(l) }</button>)
+        });
+        let original = ctx.link().callback(|_| PageMsg::Language(None));
         let alert_box = html_nested!(<AlertBox refresh={ self.alert.clone() } />);
         html! {
             <div>
-                <h1>
-                    { val.title.clone() }
-                </h1>
-
-                <p><ul>
-                    { for authors }
-                </ul></p>
-
-                <p>
-                    { val.abstraction.clone() }
-                </p>
-
-                <p>
-                    { for keywords }
-                </p>
-
-                <form enctype="multipart/form-data" onsubmit={ file_upload }><fieldset>
-                    <p><label>
-                        { "Commit Message: "}
-                        <textarea type="text" name="message" required={ true } />
-                    </label></p>
-
-                    <p><label>
-                        { "Release file: " }
-                        <input type="file" name="release" accept={ mime::APPLICATION_PDF.to_string() } required={ true } />
-                    </label></p>
-
-                    <p><label>
-                        { "Source file: " }
-                        <input type="file" name="source"
-                        accept=".tar.gz,.tgz,.tar.Z,.tar.bz2,.tbz2,.tar.lz,.tlz,.tar.xz,.txz,.tar.zst"
-                        required={ false } />
-                    </label></p>
+                if let Some(ref withdrawal) = val.withdrawal {
+                    <section role="alert">
+                        <h2>{ "Withdrawn" }</h2>
+                        <p>
+                            { "This thesis was withdrawn on " }
+                            <time datetime={ withdrawal.withdrawn_at.to_rfc3339() }>
+                                { withdrawal.withdrawn_at.format("%Y-%m-%d").to_string() }
+                            </time>
+                            if let Some(by) = withdrawal.withdrawn_by {
+                                { " by " }{ PublicProfile::tiny_from_id(by, cfg.clone()) }
+                            }
+                            { ". Its metadata and versions are kept for the record." }
+                        </p>
+                        <blockquote>{ &*withdrawal.reason }</blockquote>
+                    </section>
+                }
+
+                if self.editing {
+                    <edit::Edit
+                        cfg={ cfg.clone() }
+                        err={ ctx.link().callback(PageMsg::Err) }
+                        alert={ ctx.link().callback(PageMsg::Alert) }
+                        val={ val.clone() }
+                        refresh={ ctx.link().callback(PageMsg::Refresh) }
+                        cancel={ ctx.link().callback(|_| PageMsg::Edit(false)) }
+                    />
+                } else {
+                    if !val.translations.is_empty() {
+                        <menu>
+                            <button onclick={ original } disabled={ language.is_none() }>{ "Original" }</button>
+                            { for switch }
+                        </menu>
+                    }
+
+                    <h1 lang={ language.map(str::to_owned) }>
+                        { val.title_in(language) }
+                    </h1>
+
+                    <Bookmark cfg={ cfg.clone() } err={ ctx.link().callback(PageMsg::Err) } alert={ ctx.link().callback(PageMsg::Alert) } id={ val.id._id } />
+
+                    <p><ul>
+                        { for authors }
+                    </ul></p>
+
+                    <div lang={ language.map(str::to_owned) }>
+                        { markup::view(&val.abstraction_in(language)) }
+                    </div>
 
                     <p>
-                        <input type="submit" value="Commit" />
+                        { for keywords }
                     </p>
-                </fieldset></form>
+
+                    <Doi
+                        cfg={ cfg.clone() }
+                        err={ ctx.link().callback(PageMsg::Err) }
+                        alert={ ctx.link().callback(PageMsg::Alert) }
+                        val={ val.clone() }
+                        refresh={ ctx.link().callback(PageMsg::Refresh) }
+                        can_request={ can_request_doi }
+                    />
+
+                    if !val.languages.is_empty() {
+                        <p>
+                            { "Languages: " }
+                            { val.languages.iter().map(|l| display_name(l)).collect::<Vec<_>>().join(", ") }
+                        </p>
+                    }
+
+                    if can_edit {
+                        <p>
+                            <button onclick={ ctx.link().callback(|_| PageMsg::Edit(true)) }>{ "Edit metadata" }</button>
+                        </p>
+                    }
+
+                    if can_withdraw {
+                        <Withdraw
+                            cfg={ cfg.clone() }
+                            err={ ctx.link().callback(PageMsg::Err) }
+                            alert={ ctx.link().callback(PageMsg::Alert) }
+                            val={ val.clone() }
+                            refresh={ ctx.link().callback(PageMsg::Refresh) }
+                        />
+                    }
+
+                    if let Some(me) = self.me {
+                        <Transfer
+                            cfg={ cfg.clone() }
+                            err={ ctx.link().callback(PageMsg::Err) }
+                            alert={ ctx.link().callback(PageMsg::Alert) }
+                            val={ val.clone() }
+                            {me}
+                            accepted={ ctx.link().callback(|_| PageMsg::Reload) }
+                        />
+                    }
+                }
+
+                <ShareThesis val={ val.clone() } latest={ latest.clone() } />
+
+                <details>
+                    <summary>{ "Cite" }</summary>
+                    if self.versions.is_some() {
+                        <Suspense fallback={ html!(<p>{ "Loading citation..." }</p>) }>
+                            <Cite cfg={ cfg.clone() } val={ val.clone() } version={ latest.clone() } />
+                        </Suspense>
+                    } else {
+                        <p>{ "Loading citation..." }</p>
+                    }
+                </details>
+
+                <section>
+                    <h2>{ "Versions" }</h2>
+                    {
+                        match self.versions {
+                            Some(Ok(ref vals)) => html!(<Timeline cfg={ cfg.clone() } vals={ vals.clone() } {withdrawn} />),
+                            Some(Err(ref failure)) => failure.clone(),
+                            None => html!(<p>{ "Loading versions..." }</p>),
+                        }
+                    }
+                </section>
+
+                if !withdrawn && self.versions.is_some() {
+                    <Commit
+                        cfg={ cfg.clone() }
+                        err={ ctx.link().callback(PageMsg::Err) }
+                        alert={ ctx.link().callback(PageMsg::Alert) }
+                        id={ val.id._id }
+                        {latest}
+                        committed={ ctx.link().callback(PageMsg::Version) }
+                    />
+                }
 
                 { alert_box }
             </div>
@@ -0,0 +1,23 @@
+use std::rc::Rc;
+
+use yew::{function_component, html, Html, Properties};
+
+use crate::{
+    models::{thesis::Thesis, version::Version},
+    views::share::Share,
+};
+
+#[derive(PartialEq, Properties)]
+pub(super) struct ShareThesisProps {
+    pub(super) val: Rc<Thesis>,
+    pub(super) latest: Option<Rc<Version>>,
+}
+
+#[function_component]
+pub(super) fn ShareThesis(props: &ShareThesisProps) -> Html {
+    let val = &props.val;
+    let pinned = props.latest.as_ref().map(|latest| latest.canonical_url());
+    html! {
+        <Share title={ val.title.clone() } canonical={ val.canonical_url() } {pinned} />
+    }
+}
@@ -0,0 +1,72 @@
+use std::rc::Rc;
+
+use yew::{function_component, html, html_nested, Html, Properties};
+
+use crate::models::{
+    common::AppConfig,
+    profile::PublicProfile,
+    version::{Version, VersionState},
+};
+
+use super::changelog::Changelog;
+
+#[derive(PartialEq, Properties)]
+pub struct TimelineProps {
+    pub cfg: Rc<AppConfig>,
+    pub vals: Rc<Vec<Rc<Version>>>,
+    #[prop_or_default]
+    pub withdrawn: bool,
+}
+
+#[function_component]
+pub fn Timeline(props: &TimelineProps) -> Html {
+    let cfg = props.cfg.clone();
+    let withdrawn = props.withdrawn;
+
+    if props.vals.is_empty() {
+        return html!(<p>{ "No version has been committed yet." }</p>);
+    }
+    let mut vals = props.vals.iter().cloned().collect::<Vec<_>>();
+    vals.sort_by_key(|v| (v.major_num, v.minor_num));
+    let changelog = html!(<Changelog vals={ Rc::new(vals.clone()) } />);
+    vals.reverse();
+    let passed = vals
+        .iter()
+        .find(|v| v.state == VersionState::Passed(true))
+        .map(|v| v._id);
+    let items = vals.into_iter().map(|val| {
+        let uploader = match val.uploader_id {
+            Some(uploader_id) => PublicProfile::tiny_from_id(uploader_id, cfg.clone()),
+            None => html!({ "Unknown uploader" }),
+        };
+        let entry = html! {
+            <>
+                { val.view_tiny() }
+                { " " }{ val.uploaded_at.format("%Y-%m-%d %H:%M").to_string() }
+                { " " }{ uploader }
+                { " " }{ format!("{} downloads", val.downloads) }
+                if withdrawn {
+                    { " " }<mark>{ "Withdrawn" }</mark>
+                }
+                if !val.message.is_empty() {
+                    <blockquote>{ &*val.message }</blockquote>
+                }
+            </>
+        };
+        html_nested! {
+            <li>
+                if passed == Some(val._id) {
+                    <strong>{ entry }{ " (latest passed)" }</strong>
+                } else {
+                    { entry }
+                }
+            </li>
+        }
+    });
+    html! {
+        <>
+            <ol>{ for items }</ol>
+            { changelog }
+        </>
+    }
+}
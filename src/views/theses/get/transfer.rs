@@ -0,0 +1,75 @@
+use std::rc::Rc;
+
+use bson::oid::ObjectId;
+use yew::{function_component, html, use_state, Callback, Html, Properties, Suspense};
+
+use crate::{
+    models::{
+        common::{AppConfig, AppError, FetchOther, FetchRes},
+        thesis::Thesis,
+        transfer::Transfer as Request,
+    },
+    views::{profile_list::input::Input, transfers::Transfers},
+};
+
+#[derive(PartialEq, Properties)]
+pub(super) struct TransferProps {
+    pub(super) cfg: Rc<AppConfig>,
+    pub(super) err: Callback<AppError>,
+    pub(super) alert: Callback<FetchOther>,
+    pub(super) val: Rc<Thesis>,
+    pub(super) me: ObjectId,
+    pub(super) accepted: Callback<ObjectId>,
+}
+
+#[function_component]
+pub(super) fn Transfer(props: &TransferProps) -> Html {
+    let revision = use_state(|| 0u32);
+    let val = &props.val;
+    let me = props.me;
+    let is_owner = val.id.owner_id == me && val.withdrawal.is_none();
+
+    let validate = Callback::from(move |id: ObjectId| id != me);
+    let submit = Callback::from({
+        let cfg = props.cfg.clone();
+        let err = props.err.clone();
+        let alert = props.alert.clone();
+        let revision = revision.clone();
+        let thesis_id = val.id._id;
+        move |to_id| {
+            let cfg = cfg.clone();
+            let err = err.clone();
+            let alert = alert.clone();
+            let revision = revision.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match Request::try_create(&cfg, thesis_id, to_id).await {
+                    Ok(FetchRes::Body(_)) => revision.set(*revision + 1),
+                    Ok(FetchRes::Other(other)) => alert.emit(other),
+                    Err(e) => err.emit(e),
+                }
+            });
+        }
+    });
+    html! {
+        <>
+            <Suspense fallback={ html!() }>
+                <Transfers
+                    cfg={ props.cfg.clone() }
+                    {me}
+                    query={ vec![("thesis_id", val.id._id.to_hex().into())] }
+                    revision={ *revision }
+                    accepted={ props.accepted.clone() }
+                />
+            </Suspense>
+            if is_owner {
+                <details>
+                    <summary>{ "Transfer ownership" }</summary>
+                    <p>
+                        { "Enter the profile ID of the new owner. The thesis stays yours until they accept the transfer on their dashboard." }
+                    </p>
+                    <Input {validate} {submit} action="Request transfer" />
+                </details>
+            }
+        </>
+    }
+}
@@ -0,0 +1,103 @@
+use std::rc::Rc;
+
+use web_sys::{HtmlTextAreaElement, InputEvent, SubmitEvent};
+use yew::{function_component, html, use_state, Callback, Html, Properties, TargetCast};
+
+use crate::models::{
+    common::{AppConfig, AppError, FetchOther, FetchRes},
+    thesis::Thesis,
+};
+
+#[derive(PartialEq, Properties)]
+pub(super) struct WithdrawProps {
+    pub(super) cfg: Rc<AppConfig>,
+    pub(super) err: Callback<AppError>,
+    pub(super) alert: Callback<FetchOther>,
+    pub(super) val: Rc<Thesis>,
+    pub(super) refresh: Callback<Rc<Thesis>>,
+}
+
+#[function_component]
+pub(super) fn Withdraw(props: &WithdrawProps) -> Html {
+    let val = props.val.clone();
+    let open = use_state(|| false);
+    let reason = use_state(String::new);
+    let submitting = use_state(|| false);
+
+    if !*open {
+        let onclick = Callback::from({
+            let open = open.clone();
+            move |_| open.set(true)
+        });
+        return html! {
+            <p>
+                <button {onclick}>{ "Withdraw thesis" }</button>
+            </p>
+        };
+    }
+
+    let oninput = Callback::from({
+        let reason = reason.clone();
+        move |e: InputEvent| {
+            if let Some(a) = e.target_dyn_into::<HtmlTextAreaElement>() {
+                reason.set(a.value());
+            }
+        }
+    });
+    let onsubmit = Callback::from({
+        let cfg = props.cfg.clone();
+        let err = props.err.clone();
+        let alert = props.alert.clone();
+        let refresh = props.refresh.clone();
+        let reason = reason.clone();
+        let submitting = submitting.clone();
+        let id = val.id._id;
+        move |e: SubmitEvent| {
+            e.prevent_default();
+            let reason = reason.trim().to_owned();
+            if reason.is_empty() || *submitting {
+                return;
+            }
+            submitting.set(true);
+            let cfg = cfg.clone();
+            let err = err.clone();
+            let alert = alert.clone();
+            let refresh = refresh.clone();
+            let submitting = submitting.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let res = Thesis::try_withdraw(&cfg, id, &reason).await;
+                submitting.set(false);
+                match res {
+                    Ok(FetchRes::Body(val)) => refresh.emit(val),
+                    Ok(FetchRes::Other(other)) => alert.emit(other),
+                    Err(e) => err.emit(e),
+                }
+            });
+        }
+    });
+    let cancel = Callback::from({
+        let open = open.clone();
+        move |_| open.set(false)
+    });
+    html! {
+        <form {onsubmit}><fieldset>
+            <legend>{ "Withdraw thesis" }</legend>
+            <p>
+                { "A withdrawn thesis stays listed with its metadata, but its page shows a withdrawal notice " }
+                { "and all of its versions are marked as withdrawn. This cannot be undone." }
+            </p>
+            <p><label>
+                { "Reason: " }
+                <textarea name="reason" required={ true } value={ (*reason).clone() } {oninput} />
+            </label></p>
+            <p>
+                <input
+                    type="submit"
+                    value={ if *submitting { "Withdrawing..." } else { "Withdraw" } }
+                    disabled={ *submitting || reason.trim().is_empty() }
+                />
+                <button type="button" onclick={ cancel }>{ "Cancel" }</button>
+            </p>
+        </fieldset></form>
+    }
+}
@@ -6,27 +6,31 @@ mod input;
 mod entity;
 
 #[derive(Default)]
-pub(super) struct Keywords {
+pub(in crate::views::theses) struct Keywords {
     vals: Rc<Vec<AttrValue>>,
 }
 
-pub(super) enum KeywordsMsg {
+pub(in crate::views::theses) enum KeywordsMsg {
     Push(AttrValue),
     Remove(usize),
     New,
 }
 
 #[derive(PartialEq, Properties)]
-pub(super) struct KeywordsProps {
-    pub(super) vals: Callback<Rc<Vec<AttrValue>>>
+pub(in crate::views::theses) struct KeywordsProps {
+    pub(in crate::views::theses) vals: Callback<Rc<Vec<AttrValue>>>,
+    #[prop_or_default]
+    pub(in crate::views::theses) init: Rc<Vec<AttrValue>>,
 }
 
 impl Component for Keywords {
     type Message = KeywordsMsg;
     type Properties = KeywordsProps;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        Self::default()
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            vals: ctx.props().init.clone(),
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
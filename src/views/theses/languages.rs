@@ -0,0 +1,156 @@
+use std::{collections::BTreeSet, rc::Rc};
+
+use gloo::utils::format::JsValueSerdeExt;
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+use web_sys::{Event, HtmlInputElement, HtmlSelectElement, InputEvent};
+use yew::{html, html_nested, AttrValue, Callback, Component, Context, Html, Properties, TargetCast};
+
+use crate::models::language::{english_name, normalize_tag, COMMON_LANGUAGES};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = Intl)]
+    type DisplayNames;
+
+    #[wasm_bindgen(constructor, js_namespace = Intl, catch)]
+    fn new(locales: &JsValue, options: &JsValue) -> Result<DisplayNames, JsValue>;
+
+    #[wasm_bindgen(method, catch)]
+    fn of(this: &DisplayNames, code: &str) -> Result<JsValue, JsValue>;
+}
+
+pub(in crate::views) fn display_name(tag: &str) -> String {
+    let localized = JsValue::from_serde(&serde_json::json!({ "type": "language" }))
+        .ok()
+        .and_then(|options| DisplayNames::new(&JsValue::UNDEFINED, &options).ok())
+        .and_then(|names| names.of(tag).ok())
+        .and_then(|name| name.as_string());
+    match localized.as_deref().or_else(|| english_name(tag)) {
+        Some(name) if name != tag => format!("{name} ({tag})"),
+        _ => tag.to_owned(),
+    }
+}
+
+#[derive(Default)]
+pub(in crate::views::theses) struct Languages {
+    vals: Rc<BTreeSet<Rc<str>>>,
+    tag: AttrValue,
+}
+
+pub(in crate::views::theses) enum LanguagesMsg {
+    Input(String),
+    Push(String),
+    Remove(Rc<str>),
+}
+
+#[derive(PartialEq, Properties)]
+pub(in crate::views::theses) struct LanguagesProps {
+    pub(in crate::views::theses) vals: Callback<Rc<BTreeSet<Rc<str>>>>,
+    #[prop_or_default]
+    pub(in crate::views::theses) init: Rc<BTreeSet<Rc<str>>>,
+    #[prop_or_default]
+    pub(in crate::views::theses) suggestions: Rc<BTreeSet<Rc<str>>>,
+}
+
+impl Component for Languages {
+    type Message = LanguagesMsg;
+    type Properties = LanguagesProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            vals: ctx.props().init.clone(),
+            ..Default::default()
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            LanguagesMsg::Input(tag) => {
+                self.tag = tag.into();
+                return true;
+            }
+            LanguagesMsg::Push(tag) => {
+                if let Some(tag) = normalize_tag(&tag) {
+                    Rc::make_mut(&mut self.vals).insert(tag.into());
+                    self.tag = AttrValue::default();
+                }
+            }
+            LanguagesMsg::Remove(tag) => {
+                Rc::make_mut(&mut self.vals).remove(&tag);
+            }
+        }
+        ctx.props().vals.emit(self.vals.clone());
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let chosen = self.vals.iter().map(|tag| {
+            let onclick = ctx.link().callback({
+                let tag = tag.clone();
+                move |_| LanguagesMsg::Remove(tag.clone())
+            });
+            html_nested! {
+                <li>
+                    { display_name(tag) }
+                    <label {onclick}>{ "❎" }</label>
+                </li>
+            }
+        });
+        let suggestions = ctx
+            .props()
+            .suggestions
+            .iter()
+            .filter(|tag| !self.vals.contains(*tag))
+            .map(|tag| {
+                let onclick = ctx.link().callback({
+                    let tag = tag.to_string();
+                    move |_| LanguagesMsg::Push(tag.clone())
+                });
+                html_nested! {
+                    <button {onclick}>{ format!("Accept {}", display_name(tag)) }</button>
+                }
+            })
+            .collect::<Vec<_>>();
+        let common = COMMON_LANGUAGES
+            .iter()
+            .filter(|(tag, _)| !self.vals.contains(*tag))
+            .map(|(tag, _)| html_nested!(<option value={ *tag }>{ display_name(tag) }</option>));
+        let pick = ctx.link().batch_callback(|e: Event| {
+            e.target_dyn_into::<HtmlSelectElement>().and_then(|s| {
+                let tag = s.value();
+                s.set_value("");
+                (!tag.is_empty()).then_some(LanguagesMsg::Push(tag))
+            })
+        });
+        let oninput = ctx.link().batch_callback(|e: InputEvent| {
+            e.target_dyn_into::<HtmlInputElement>()
+                .map(|i| LanguagesMsg::Input(i.value()))
+        });
+        let valid = normalize_tag(&self.tag).filter(|tag| !self.vals.contains(tag.as_str()));
+        let add = ctx.link().callback({
+            let tag = self.tag.to_string();
+            move |_| LanguagesMsg::Push(tag.clone())
+        });
+        html! {
+            <fieldset>
+                <legend>{ "Languages" }</legend>
+                <menu>
+                    { for chosen }
+                </menu>
+                if !suggestions.is_empty() {
+                    <p>
+                        { "Detected: " }
+                        { for suggestions }
+                    </p>
+                }
+                <select onchange={ pick }>
+                    <option value="" selected={ true }>{ "Add a common language..." }</option>
+                    { for common }
+                </select>
+                <input type="text" placeholder="Other BCP 47 tag, e.g. pt-BR" {oninput} value={ self.tag.clone() } />
+                { if self.tag.is_empty() { "" } else if valid.is_some() { " ✅" } else { " ❎" } }
+                <button onclick={ add } disabled={ valid.is_none() }>{ "Add" }</button>
+            </fieldset>
+        }
+    }
+}
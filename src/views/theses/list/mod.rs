@@ -0,0 +1,182 @@
+use std::rc::Rc;
+
+use web_sys::{Event, HtmlInputElement, InputEvent, SubmitEvent};
+use yew::{html, html_nested, Component, Context, Html, Properties, TargetCast};
+
+use crate::{
+    models::{
+        common::{AppConfig, AppError, FetchOther, FetchRes},
+        search::ThesisQuery,
+        thesis::Thesis,
+    },
+    views::alerts::AlertBox,
+};
+
+mod saved;
+
+#[derive(Default)]
+pub(super) struct List {
+    err: Option<AppError>,
+    alert: Option<Rc<FetchOther>>,
+    query: ThesisQuery,
+    vals: Option<Rc<Vec<Rc<Thesis>>>>,
+}
+
+pub(super) enum ListMsg {
+    Err(AppError),
+    Alert(FetchOther),
+    InputQ(String),
+    InputKeyword(String),
+    InputLanguage(String),
+    PassedOnly(bool),
+    Search,
+    Apply(ThesisQuery),
+    Found(Rc<Vec<Rc<Thesis>>>),
+}
+
+#[derive(PartialEq, Properties)]
+pub(super) struct ListProps {
+    pub(super) cfg: Rc<AppConfig>,
+}
+
+impl Component for List {
+    type Message = ListMsg;
+    type Properties = ListProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_message(ListMsg::Search);
+        Self::default()
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            ListMsg::Err(e) => {
+                self.err = Some(e);
+                true
+            }
+            ListMsg::Alert(other) => {
+                self.alert = Some(Rc::new(other));
+                true
+            }
+            ListMsg::InputQ(q) => {
+                self.query.q = q.into();
+                true
+            }
+            ListMsg::InputKeyword(keyword) => {
+                self.query.keyword = keyword.into();
+                true
+            }
+            ListMsg::InputLanguage(language) => {
+                self.query.language = language.into();
+                true
+            }
+            ListMsg::PassedOnly(is_passed) => {
+                self.query.is_passed = is_passed;
+                true
+            }
+            ListMsg::Apply(query) => {
+                self.query = query;
+                ctx.link().send_message(ListMsg::Search);
+                true
+            }
+            ListMsg::Search => {
+                ctx.link().send_future({
+                    let cfg = ctx.props().cfg.clone();
+                    let query = self.query.clone();
+                    async move {
+                        match Thesis::try_list(&cfg, &query.pairs()).await {
+                            Ok(FetchRes::Body(vals)) => ListMsg::Found(vals),
+                            Ok(FetchRes::Other(other)) => ListMsg::Alert(other),
+                            Err(e) => ListMsg::Err(e),
+                        }
+                    }
+                });
+                false
+            }
+            ListMsg::Found(vals) => {
+                self.vals = Some(vals);
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if let Some(ref err) = self.err {
+            return err.view();
+        }
+
+        let input_q = ctx.link().batch_callback(|e: InputEvent| {
+            e.target_dyn_into::<HtmlInputElement>()
+                .map(|i| ListMsg::InputQ(i.value()))
+        });
+        let input_keyword = ctx.link().batch_callback(|e: InputEvent| {
+            e.target_dyn_into::<HtmlInputElement>()
+                .map(|i| ListMsg::InputKeyword(i.value()))
+        });
+        let input_language = ctx.link().batch_callback(|e: InputEvent| {
+            e.target_dyn_into::<HtmlInputElement>()
+                .map(|i| ListMsg::InputLanguage(i.value()))
+        });
+        let passed_only = ctx.link().batch_callback(|e: Event| {
+            e.target_dyn_into::<HtmlInputElement>()
+                .map(|i| ListMsg::PassedOnly(i.checked()))
+        });
+        let onsubmit = ctx.link().callback(|e: SubmitEvent| {
+            e.prevent_default();
+            ListMsg::Search
+        });
+        let err = ctx.link().callback(ListMsg::Err);
+        let alert = ctx.link().callback(ListMsg::Alert);
+        let apply = ctx.link().callback(ListMsg::Apply);
+
+        let results = match self.vals {
+            None => html!(<p>{ "Searching..." }</p>),
+            Some(ref vals) if vals.is_empty() => html!(<p>{ "No thesis matches." }</p>),
+            Some(ref vals) => {
+                let items = vals.iter().map(|val| {
+                    html_nested! {
+                        <li>
+                            { val.view_tiny() }
+                            { " " }{ val.id.created_at.format("%Y-%m-%d").to_string() }
+                            { for val.keywords.iter().map(|k| html_nested!(<b>{ "&nbsp;" }{ k }</b>)) }
+                        </li>
+                    }
+                });
+                html!(<ul>{ for items }</ul>)
+            }
+        };
+        let alert_box = html_nested!(<AlertBox refresh={ self.alert.clone() } />);
+        html! {
+            <div>
+                <h1>{ "Theses" }</h1>
+
+                <form {onsubmit}><fieldset>
+                    <legend>{ "Search" }</legend>
+                    <label>
+                        { "Text: " }
+                        <input type="search" oninput={ input_q } value={ self.query.q.to_string() } />
+                    </label>
+                    <label>
+                        { " Key word: " }
+                        <input type="text" oninput={ input_keyword } value={ self.query.keyword.to_string() } />
+                    </label>
+                    <label>
+                        { " Language: " }
+                        <input type="text" oninput={ input_language } value={ self.query.language.to_string() } />
+                    </label>
+                    <label>
+                        { " Passed only: " }
+                        <input type="checkbox" onchange={ passed_only } checked={ self.query.is_passed } />
+                    </label>
+                    <input type="submit" value="Search" />
+                </fieldset></form>
+
+                <saved::Saved cfg={ ctx.props().cfg.clone() } {err} {alert} query={ self.query.clone() } {apply} />
+
+                { results }
+
+                { alert_box }
+            </div>
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use std::{collections::HashMap, rc::Rc};
+
+use bson::oid::ObjectId;
+use web_sys::{HtmlInputElement, InputEvent};
+use yew::{html, html_nested, AttrValue, Callback, Component, Context, Html, Properties, TargetCast};
+
+use crate::models::{
+    common::{AppConfig, AppError, FetchOther, FetchRes},
+    search::{SavedSearch, SearchStore, ThesisQuery},
+    thesis::Thesis,
+};
+
+#[derive(Default)]
+pub(super) struct Saved {
+    store: SearchStore,
+    vals: Rc<Vec<Rc<SavedSearch>>>,
+    counts: HashMap<ObjectId, usize>,
+    name: AttrValue,
+}
+
+pub(super) enum SavedMsg {
+    Err(AppError),
+    Alert(FetchOther),
+    Loaded(SearchStore, Rc<Vec<Rc<SavedSearch>>>),
+    Count(ObjectId, usize),
+    InputName(String),
+    Save,
+    Put(Rc<SavedSearch>),
+    Apply(usize),
+    Remove(usize),
+    Removed(ObjectId),
+}
+
+#[derive(PartialEq, Properties)]
+pub(super) struct SavedProps {
+    pub(super) cfg: Rc<AppConfig>,
+    pub(super) err: Callback<AppError>,
+    pub(super) alert: Callback<FetchOther>,
+    pub(super) query: ThesisQuery,
+    pub(super) apply: Callback<ThesisQuery>,
+}
+
+impl Saved {
+    fn persist(&self, ctx: &Context<Self>, val: SavedSearch) {
+        match self.store {
+            SearchStore::Server => ctx.link().send_future({
+                let cfg = ctx.props().cfg.clone();
+                async move {
+                    match val.try_put(&cfg).await {
+                        Ok(FetchRes::Body(val)) => SavedMsg::Put(val),
+                        Ok(FetchRes::Other(other)) => SavedMsg::Alert(other),
+                        Err(e) => SavedMsg::Err(e),
+                    }
+                }
+            }),
+            SearchStore::Local => ctx.link().send_message(SavedMsg::Put(Rc::new(val))),
+        }
+    }
+
+    fn count(ctx: &Context<Self>, val: Rc<SavedSearch>) {
+        ctx.link().send_future({
+            let cfg = ctx.props().cfg.clone();
+            async move {
+                let since = val.visited_at.to_rfc3339();
+                let mut query = val.query.pairs();
+                query.push(("since", &since));
+                match Thesis::try_list(&cfg, &query).await {
+                    Ok(FetchRes::Body(theses)) => SavedMsg::Count(
+                        val._id,
+                        theses.iter().filter(|t| t.id.created_at > val.visited_at).count(),
+                    ),
+                    Ok(FetchRes::Other(other)) => SavedMsg::Alert(other),
+                    Err(e) => SavedMsg::Err(e),
+                }
+            }
+        });
+    }
+}
+
+impl Component for Saved {
+    type Message = SavedMsg;
+    type Properties = SavedProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_future({
+            let cfg = ctx.props().cfg.clone();
+            async move {
+                match SavedSearch::try_list(&cfg).await {
+                    Ok(FetchRes::Body(vals)) => SavedMsg::Loaded(SearchStore::Server, vals),
+                    Ok(FetchRes::Other(other)) if matches!(other.status, 401 | 403) => {
+                        SavedMsg::Loaded(SearchStore::Local, Rc::new(SavedSearch::load_local()))
+                    }
+                    Ok(FetchRes::Other(other)) => SavedMsg::Alert(other),
+                    Err(e) => SavedMsg::Err(e),
+                }
+            }
+        });
+        Self::default()
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            SavedMsg::Err(e) => {
+                ctx.props().err.emit(e);
+                false
+            }
+            SavedMsg::Alert(other) => {
+                ctx.props().alert.emit(other);
+                false
+            }
+            SavedMsg::Loaded(store, vals) => {
+                for val in vals.iter() {
+                    Self::count(ctx, val.clone());
+                }
+                self.store = store;
+                self.vals = vals;
+                true
+            }
+            SavedMsg::Count(id, count) => {
+                self.counts.insert(id, count);
+                true
+            }
+            SavedMsg::InputName(name) => {
+                self.name = name.into();
+                true
+            }
+            SavedMsg::Save => {
+                let val = SavedSearch::new(self.name.as_str().into(), ctx.props().query.clone());
+                self.name = AttrValue::default();
+                self.persist(ctx, val);
+                true
+            }
+            SavedMsg::Put(val) => {
+                let vals = Rc::make_mut(&mut self.vals);
+                match vals.iter_mut().find(|v| v._id == val._id) {
+                    Some(old) => *old = val.clone(),
+                    None => vals.push(val.clone()),
+                }
+                if self.store == SearchStore::Local {
+                    SavedSearch::store_local(&self.vals);
+                }
+                self.counts.insert(val._id, 0);
+                true
+            }
+            SavedMsg::Apply(index) => {
+                if let Some(val) = self.vals.get(index) {
+                    ctx.props().apply.emit(val.query.clone());
+                    self.persist(ctx, SavedSearch {
+                        visited_at: chrono::Utc::now(),
+                        ..(**val).clone()
+                    });
+                }
+                false
+            }
+            SavedMsg::Remove(index) => {
+                if let Some(id) = self.vals.get(index).map(|val| val._id) {
+                    match self.store {
+                        SearchStore::Server => ctx.link().send_future({
+                            let cfg = ctx.props().cfg.clone();
+                            async move {
+                                match SavedSearch::try_delete(&cfg, id).await {
+                                    Ok(None) => SavedMsg::Removed(id),
+                                    Ok(Some(other)) => SavedMsg::Alert(other),
+                                    Err(e) => SavedMsg::Err(e),
+                                }
+                            }
+                        }),
+                        SearchStore::Local => ctx.link().send_message(SavedMsg::Removed(id)),
+                    }
+                }
+                false
+            }
+            SavedMsg::Removed(id) => {
+                Rc::make_mut(&mut self.vals).retain(|v| v._id != id);
+                if self.store == SearchStore::Local {
+                    SavedSearch::store_local(&self.vals);
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let input_name = ctx.link().batch_callback(|e: InputEvent| {
+            e.target_dyn_into::<HtmlInputElement>()
+                .map(|i| SavedMsg::InputName(i.value()))
+        });
+        let save = ctx.link().callback(|_| SavedMsg::Save);
+        let searches = self.vals.iter().enumerate().map(|(index, val)| {
+            let apply = ctx.link().callback(move |_| SavedMsg::Apply(index));
+            let remove = ctx.link().callback(move |_| SavedMsg::Remove(index));
+            let count = self.counts.get(&val._id).copied().unwrap_or_default();
+            html_nested! {
+                <li>
+                    <button onclick={ apply }>{ val.name.clone() }</button>
+                    if count > 0 {
+                        <mark>{ format!("{count} new") }</mark>
+                    }
+                    <label onclick={ remove }>{ "❎" }</label>
+                </li>
+            }
+        });
+        html! {
+            <fieldset>
+                <legend>{ "Saved Searches" }</legend>
+                <menu>
+                    { for searches }
+                </menu>
+                <input type="text" placeholder="Name this search" oninput={ input_name } value={ self.name.clone() } />
+                <button onclick={ save } disabled={ self.name.is_empty() }>{ "Save search" }</button>
+                if self.store == SearchStore::Local {
+                    <small>{ " Saved in this browser only." }</small>
+                }
+            </fieldset>
+        }
+    }
+}
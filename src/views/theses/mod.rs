@@ -1,12 +1,19 @@
 mod route;
 mod get;
 mod post;
+mod list;
+mod keywords;
+mod languages;
+mod translations;
 
 pub(super) use route::Route;
 
 impl Route {
     pub(super) fn switch(cfg: std::rc::Rc<crate::models::common::AppConfig>) -> impl Fn(Self) -> yew::Html {
         move |routes| match routes {
+            Self::List => yew::html! {
+                <list::List cfg={cfg.clone()} />
+            },
             Self::New => yew::html! {
                 <post::Post cfg={cfg.clone()} />
             },
@@ -18,7 +25,6 @@ impl Route {
                     </yew::Suspense>
                     }
                 }
-            _ => yew::html!()
         }
     }
 }
\ No newline at end of file
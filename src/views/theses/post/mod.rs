@@ -1,4 +1,8 @@
-use std::{ops::Not, rc::Rc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Not,
+    rc::Rc,
+};
 
 use web_sys::{HtmlInputElement, InputEvent, HtmlTextAreaElement, RequestCredentials};
 use yew::{html, html_nested, AttrValue, Component, Context, Html, Properties, TargetCast};
@@ -7,13 +11,13 @@ use yew_router::scope_ext::RouterScopeExt;
 use crate::{
     models::{
         common::{AppConfig, AppError, FetchOther},
+        language::detect_tag,
         profile::PublicProfile,
+        thesis::Translation,
     },
-    views::{alerts::AlertBox, profile_list::ProfileList},
+    views::{alerts::AlertBox, markup, profile_list::ProfileList, theses::{keywords, languages, translations}},
 };
 
-mod keywords;
-
 #[derive(Default)]
 pub(super) struct Post {
     err: Option<AppError>,
@@ -23,6 +27,20 @@ pub(super) struct Post {
     abstraction: AttrValue,
     keywords: Rc<Vec<AttrValue>>,
     authors: Rc<Vec<Rc<PublicProfile>>>,
+    languages: Rc<BTreeSet<Rc<str>>>,
+    translations: Rc<BTreeMap<Rc<str>, Translation>>,
+    detected: [Option<&'static str>; 2],
+    suggestions: Rc<BTreeSet<Rc<str>>>,
+}
+
+impl Post {
+    fn detect(&mut self, i: usize, text: &str) {
+        let tag = detect_tag(text);
+        if self.detected[i] != tag {
+            self.detected[i] = tag;
+            self.suggestions = Rc::new(self.detected.into_iter().flatten().map(Rc::from).collect());
+        }
+    }
 }
 
 pub(super) enum PostMsg {
@@ -32,6 +50,8 @@ pub(super) enum PostMsg {
     InputAbstraction(String),
     UpdateKeywords(Rc<Vec<AttrValue>>),
     UpdateAuthors(Rc<Vec<Rc<PublicProfile>>>),
+    UpdateLanguages(Rc<BTreeSet<Rc<str>>>),
+    UpdateTranslations(Rc<BTreeMap<Rc<str>, Translation>>),
     Post(bson::oid::ObjectId),
 }
 
@@ -59,10 +79,12 @@ impl Component for Post {
                 true
             }
             PostMsg::InputTitle(title) => {
+                self.detect(0, &title);
                 self.title = title.into();
                 true
             }
             PostMsg::InputAbstraction(abstraction) => {
+                self.detect(1, &abstraction);
                 self.abstraction = abstraction.into();
                 true
             }
@@ -74,6 +96,14 @@ impl Component for Post {
                 self.authors = authors;
                 false
             }
+            PostMsg::UpdateLanguages(languages) => {
+                self.languages = languages;
+                true
+            }
+            PostMsg::UpdateTranslations(translations) => {
+                self.translations = translations;
+                false
+            }
             PostMsg::Post(id) => {
                 if let Some(navigator) = ctx.link().navigator() {
                     navigator.push(&super::route::Route::View { id })
@@ -100,6 +130,9 @@ impl Component for Post {
         });
         let keywords = ctx.link().callback(PostMsg::UpdateKeywords);
         let authors = ctx.link().callback(PostMsg::UpdateAuthors);
+        let languages = ctx.link().callback(PostMsg::UpdateLanguages);
+        let update_translations = ctx.link().callback(PostMsg::UpdateTranslations);
+        let suggestions = self.suggestions.clone();
 
         let onclick = ctx.link().callback_future({
             let cfg = ctx.props().cfg.clone();
@@ -107,19 +140,24 @@ impl Component for Post {
             let abstraction = self.abstraction.clone();
             let keywords = self.keywords.clone();
             let authors = self.authors.clone();
+            let languages = self.languages.clone();
+            let translations = self.translations.clone();
             move |_| {
                 let cfg = cfg.clone();
                 let title = title.clone();
                 let abstraction = abstraction.clone();
                 let keywords = keywords.clone();
                 let authors = authors.clone();
+                let languages = languages.clone();
+                let translations = translations::retain(&translations, &languages);
                 async move {
                     let body = serde_json::json!({
                         "title": title.as_str(),
                         "abstraction": abstraction.as_str(),
                         "keywords": keywords.iter().map(|k| k.as_str()).collect::<Vec<_>>(),
                         "author_ids": authors.iter().map(|a| a._id).collect::<Vec<_>>(),
-                        "languages": ["en"]
+                        "languages": *languages,
+                        "translations": translations
                     });
                     match async move {
                         Ok(
@@ -152,13 +190,14 @@ impl Component for Post {
                     </label>
                 </p>
 
-                <p>
+                <div>
                     <label>
                         { "Abstraction: " }<br />
                         <textarea type="text" oninput={ check_abstraction } value={ self.abstraction.clone() } />
                         { if self.abstraction.len() >= 140 { " ✅" } else { " ❎" }}
                     </label>
-                </p>
+                    { markup::preview(&self.abstraction) }
+                </div>
 
                 <p>
                     <ProfileList cfg={ctx.props().cfg.clone()} {err} {alert} vals={authors} />
@@ -169,7 +208,15 @@ impl Component for Post {
                 </p>
 
                 <p>
-                    <button {onclick} >{ "submit" }</button>
+                    <languages::Languages vals={ languages } {suggestions} />
+                </p>
+
+                <p>
+                    <translations::Translations languages={ self.languages.clone() } vals={ update_translations } />
+                </p>
+
+                <p>
+                    <button {onclick} disabled={ self.languages.is_empty() }>{ "submit" }</button>
                 </p>
 
                 { alert_box }
@@ -0,0 +1,138 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+};
+
+use web_sys::{HtmlInputElement, HtmlTextAreaElement, InputEvent};
+use yew::{html, html_nested, Callback, Component, Context, Html, Properties, TargetCast};
+
+use crate::{models::thesis::Translation, views::theses::languages::display_name};
+
+pub(in crate::views::theses) fn retain(
+    vals: &BTreeMap<Rc<str>, Translation>,
+    languages: &BTreeSet<Rc<str>>,
+) -> BTreeMap<Rc<str>, Translation> {
+    vals.iter()
+        .filter(|(l, t)| languages.contains(*l) && !(t.title.is_empty() && t.abstraction.is_empty()))
+        .map(|(l, t)| (l.clone(), t.clone()))
+        .collect()
+}
+
+#[derive(Default)]
+pub(in crate::views::theses) struct Translations {
+    active: Option<Rc<str>>,
+    vals: Rc<BTreeMap<Rc<str>, Translation>>,
+}
+
+pub(in crate::views::theses) enum TranslationsMsg {
+    Select(Rc<str>),
+    InputTitle(String),
+    InputAbstraction(String),
+}
+
+#[derive(PartialEq, Properties)]
+pub(in crate::views::theses) struct TranslationsProps {
+    pub(in crate::views::theses) languages: Rc<BTreeSet<Rc<str>>>,
+    pub(in crate::views::theses) vals: Callback<Rc<BTreeMap<Rc<str>, Translation>>>,
+    #[prop_or_default]
+    pub(in crate::views::theses) init: Rc<BTreeMap<Rc<str>, Translation>>,
+}
+
+impl Translations {
+    fn emit(&self, ctx: &Context<Self>) {
+        ctx.props().vals.emit(Rc::new(retain(&self.vals, &ctx.props().languages)));
+    }
+}
+
+impl Component for Translations {
+    type Message = TranslationsMsg;
+    type Properties = TranslationsProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            active: None,
+            vals: ctx.props().init.clone(),
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        if self.active.as_ref().is_some_and(|l| !ctx.props().languages.contains(l)) {
+            self.active = None;
+        }
+        true
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            TranslationsMsg::Select(language) => {
+                self.active = Some(language);
+                return true;
+            }
+            TranslationsMsg::InputTitle(title) => {
+                if let Some(ref language) = self.active {
+                    Rc::make_mut(&mut self.vals).entry(language.clone()).or_default().title = title.into();
+                }
+            }
+            TranslationsMsg::InputAbstraction(abstraction) => {
+                if let Some(ref language) = self.active {
+                    Rc::make_mut(&mut self.vals).entry(language.clone()).or_default().abstraction =
+                        abstraction.into();
+                }
+            }
+        }
+        self.emit(ctx);
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if ctx.props().languages.is_empty() {
+            return Html::default();
+        }
+        let tabs = ctx.props().languages.iter().map(|language| {
+            let onclick = ctx.link().callback({
+                let language = language.clone();
+                move |_| TranslationsMsg::Select(language.clone())
+            });
+            let active = self.active.as_ref() == Some(language);
+            html_nested! {
+                <button {onclick} disabled={ active }>{ display_name(language) }</button>
+            }
+        });
+        let panel = self.active.as_ref().map(|language| {
+            let val = self.vals.get(language).cloned().unwrap_or_default();
+            let input_title = ctx.link().batch_callback(|e: InputEvent| {
+                e.target_dyn_into::<HtmlInputElement>()
+                    .map(|i| TranslationsMsg::InputTitle(i.value()))
+            });
+            let input_abstraction = ctx.link().batch_callback(|e: InputEvent| {
+                e.target_dyn_into::<HtmlTextAreaElement>()
+                    .map(|i| TranslationsMsg::InputAbstraction(i.value()))
+            });
+            html! {
+                <>
+                    <p>
+                        <label>
+                            { "Title: " }
+                            <input type="text" lang={ language.to_string() } oninput={ input_title } value={ val.title.to_string() } />
+                        </label>
+                    </p>
+                    <p>
+                        <label>
+                            { "Abstraction: " }<br />
+                            <textarea lang={ language.to_string() } oninput={ input_abstraction } value={ val.abstraction.to_string() } />
+                        </label>
+                    </p>
+                </>
+            }
+        });
+        html! {
+            <fieldset>
+                <legend>{ "Translations" }</legend>
+                <menu>
+                    { for tabs }
+                </menu>
+                { for panel }
+            </fieldset>
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use std::rc::Rc;
+
+use bson::oid::ObjectId;
+use yew::{Html, html, function_component, Properties, HtmlResult, suspense::use_future, Suspense};
+use yew_router::{prelude::Link, Routable};
+
+use crate::{models::{thesis::Thesis, common::{AppConfig, FetchRes}}, views::{alerts::AlertBox, theses::Route}};
+
+impl Thesis {
+    pub fn view_tiny(&self) -> Html {
+        html! {
+            <Link<Route> to={ Route::View { id: self.id._id } }>
+                { self.title.clone() }
+                if self.withdrawal.is_some() {
+                    { " (withdrawn)" }
+                }
+            </Link<Route>>
+        }
+    }
+
+    pub fn canonical_url(&self) -> String {
+        let origin = gloo::utils::window().location().origin().unwrap_or_default();
+        format!("{}{}", origin, Route::View { id: self.id._id }.to_path())
+    }
+
+    pub fn tiny_from_id(id: ObjectId, cfg: Rc<AppConfig>) -> Html {
+        let fallback = html! {<span>{format!("Loading Thesis {id}...")}</span>};
+
+        html! {
+            <Suspense {fallback}>
+                <TinyThesis {cfg} {id} />
+            </Suspense>
+        }
+    }
+}
+
+#[derive(PartialEq, Properties)]
+struct TinyThesisProps {
+    cfg: Rc<AppConfig>,
+    id: ObjectId,
+}
+
+#[function_component]
+fn TinyThesis(props: &TinyThesisProps) -> HtmlResult {
+    let cfg = props.cfg.clone();
+    let id = props.id;
+
+    let res = use_future(move || async move {
+        Thesis::try_get(&cfg, id).await
+    })?;
+    let res = match *res {
+        Ok(ref res) => match res {
+            FetchRes::Body(val) => val.view_tiny(),
+            FetchRes::Other(other) => html!(<AlertBox refresh={ Some(Rc::new(other.to_owned())) } />),
+        },
+        Err(ref e) => e.view(),
+    };
+    Ok(res)
+}
@@ -0,0 +1,40 @@
+use yew::{Html, html};
+use yew_router::{prelude::Link, Routable};
+
+use crate::{models::version::{Version, VersionState}, views::{theses, Route}};
+
+impl VersionState {
+    pub fn view(&self) -> Html {
+        let state = match self {
+            VersionState::Uploaded => "Uploaded",
+            VersionState::Reviewing => "Reviewing",
+            VersionState::Passed(true) => "Passed",
+            VersionState::Passed(false) => "Rejected",
+            VersionState::History => "History",
+        };
+        html!(<mark>{ state }</mark>)
+    }
+}
+
+impl Version {
+    pub fn view_tiny(&self) -> Html {
+        html! {
+            <>
+                <Link<Route> to={ Route::Versions { id: self._id } }>
+                    { "Version " }{ self.major_num }{ "." }{ self.minor_num }
+                </Link<Route>>
+                { " " }{ self.state.view() }
+            </>
+        }
+    }
+
+    pub fn canonical_url(&self) -> String {
+        let origin = gloo::utils::window().location().origin().unwrap_or_default();
+        format!("{}{}", origin, Route::Versions { id: self._id }.to_path())
+    }
+
+    pub fn thesis_url(&self) -> String {
+        let origin = gloo::utils::window().location().origin().unwrap_or_default();
+        format!("{}{}", origin, theses::Route::View { id: self.thesis_id }.to_path())
+    }
+}
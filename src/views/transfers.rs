@@ -0,0 +1,129 @@
+use std::rc::Rc;
+
+use bson::oid::ObjectId;
+use yew::{
+    function_component, html, html_nested, suspense::use_future_with_deps, use_state, AttrValue, Callback, Html,
+    HtmlResult, Properties,
+};
+
+use crate::{
+    models::{
+        common::{AppConfig, FetchRes},
+        profile::PublicProfile,
+        thesis::Thesis,
+        transfer::Transfer,
+    },
+    views::alerts::AlertBox,
+};
+
+#[derive(PartialEq, Properties)]
+pub(crate) struct TransfersProps {
+    pub(crate) cfg: Rc<AppConfig>,
+    pub(crate) me: ObjectId,
+    pub(crate) query: Vec<(&'static str, AttrValue)>,
+    #[prop_or_default]
+    pub(crate) revision: u32,
+    #[prop_or_default]
+    pub(crate) with_thesis: bool,
+    #[prop_or_default]
+    pub(crate) placeholder: Option<AttrValue>,
+    #[prop_or_default]
+    pub(crate) accepted: Callback<ObjectId>,
+}
+
+#[function_component]
+pub(crate) fn Transfers(props: &TransfersProps) -> HtmlResult {
+    let cfg = props.cfg.clone();
+    let me = props.me;
+    let changes = use_state(|| 0u32);
+    let failure = use_state(|| None::<Html>);
+
+    let res = use_future_with_deps({
+        let cfg = cfg.clone();
+        let query = props.query.clone();
+        move |_| async move {
+            let query = query.iter().map(|(k, v)| (*k, v.as_str())).collect::<Vec<_>>();
+            Transfer::try_list(&cfg, &query).await
+        }
+    }, (props.revision, *changes))?;
+    let vals = match *res {
+        Ok(FetchRes::Body(ref vals)) => vals.clone(),
+        Ok(FetchRes::Other(ref other)) => return Ok(html!(<AlertBox refresh={ Some(Rc::new(other.to_owned())) } />)),
+        Err(ref e) => return Ok(e.view()),
+    };
+    if vals.is_empty() {
+        return Ok(match props.placeholder {
+            Some(ref placeholder) => html!(<p>{ placeholder.clone() }</p>),
+            None => html!(),
+        });
+    }
+
+    let act = |val: &Rc<Transfer>, accept: bool| {
+        let cfg = cfg.clone();
+        let val = val.clone();
+        let changes = changes.clone();
+        let failure = failure.clone();
+        let accepted = props.accepted.clone();
+        Callback::from(move |_| {
+            let cfg = cfg.clone();
+            let val = val.clone();
+            let changes = changes.clone();
+            let failure = failure.clone();
+            let accepted = accepted.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let res = match accept {
+                    true => val.try_accept(&cfg).await,
+                    false => val.try_delete(&cfg).await,
+                };
+                match res {
+                    Ok(None) => {
+                        failure.set(None);
+                        if accept {
+                            accepted.emit(val.thesis_id);
+                        }
+                        changes.set(*changes + 1);
+                    }
+                    Ok(Some(other)) => failure.set(Some(html!(<AlertBox refresh={ Some(Rc::new(other)) } />))),
+                    Err(e) => failure.set(Some(e.view())),
+                }
+            });
+        })
+    };
+    let items = vals.iter().map(|val| {
+        let from = PublicProfile::tiny_from_id(val.from_id, cfg.clone());
+        let to = PublicProfile::tiny_from_id(val.to_id, cfg.clone());
+        let entry = if val.to_id == me {
+            html! {
+                <>
+                    { from }{ " offers you the ownership." }
+                    <button onclick={ act(val, true) }>{ "Accept" }</button>
+                    <button onclick={ act(val, false) }>{ "Decline" }</button>
+                </>
+            }
+        } else if val.from_id == me {
+            html! {
+                <>
+                    { "Waiting for " }{ to }{ " to accept the ownership." }
+                    <button onclick={ act(val, false) }>{ "Cancel" }</button>
+                </>
+            }
+        } else {
+            html!(<>{ "Ownership is being transferred from " }{ from }{ " to " }{ to }{ "." }</>)
+        };
+        html_nested! {
+            <li>
+                if props.with_thesis {
+                    { Thesis::tiny_from_id(val.thesis_id, cfg.clone()) }{ ": " }
+                }
+                { entry }
+                <small>{ " Requested on " }{ val.requested_at.format("%Y-%m-%d").to_string() }{ "." }</small>
+            </li>
+        }
+    });
+    Ok(html! {
+        <>
+            <ul>{ for items }</ul>
+            { for (*failure).clone() }
+        </>
+    })
+}
@@ -87,7 +87,7 @@ impl Component for Adjudge {
             }
         });
         html! {
-            <fieldset>
+            <fieldset id="adjudge">
                 <legend>{ "Adjudge" }</legend>
                 <label>
                     { "Pass: " }
@@ -0,0 +1,231 @@
+use std::{collections::BTreeSet, rc::Rc};
+
+use web_sys::RequestCredentials;
+use yew::{html, html_nested, Callback, Component, Context, Html, Properties};
+
+use crate::{
+    models::{
+        archive,
+        common::{AppConfig, AppError, FetchOther, FetchRes},
+        version::Version,
+    },
+    views::sizes,
+};
+
+const BUNDLE_LIMIT: u64 = 256 << 20;
+
+#[derive(Default)]
+pub(super) struct Downloads {
+    bundling: bool,
+    too_large: bool,
+    bundle: Option<gloo::file::ObjectUrl>,
+}
+
+pub(super) enum DownloadsMsg {
+    Err(AppError),
+    Alert(FetchOther),
+    Bundle,
+    Bundled(Vec<u8>),
+    TooLarge,
+}
+
+#[derive(PartialEq, Properties)]
+pub(super) struct DownloadsProps {
+    pub(super) cfg: Rc<AppConfig>,
+    pub(super) err: Callback<AppError>,
+    pub(super) alert: Callback<FetchOther>,
+    pub(super) val: Rc<Version>,
+}
+
+async fn fetch(cfg: &AppConfig, file_id: bson::oid::ObjectId) -> Result<Vec<u8>, DownloadsMsg> {
+    let href = cfg
+        .api
+        .join(&format!("files/{}", file_id.to_hex()))
+        .map_err(|e| DownloadsMsg::Err(e.into()))?;
+    let res = <FetchRes<Vec<u8>>>::try_binary_from_gloo_res(
+        gloo::net::http::Request::get(href.as_str())
+            .credentials(RequestCredentials::Include)
+            .send()
+            .await,
+    )
+    .await;
+    match res {
+        Ok(FetchRes::Body(bytes)) => Ok(bytes),
+        Ok(FetchRes::Other(other)) => Err(DownloadsMsg::Alert(other)),
+        Err(e) => Err(DownloadsMsg::Err(e)),
+    }
+}
+
+fn supplement_name(name: &str, file_id: bson::oid::ObjectId, taken: &mut BTreeSet<String>) -> String {
+    let name = match name.rsplit(['/', '\\']).next().unwrap_or_default().trim() {
+        "" | "." | ".." => file_id.to_hex(),
+        name => name.to_owned(),
+    };
+    let (stem, ext) = match name.rfind('.') {
+        Some(dot) if dot > 0 => name.split_at(dot),
+        _ => (name.as_str(), ""),
+    };
+    let mut unique = name.clone();
+    let mut n = 1;
+    while !taken.insert(unique.clone()) {
+        n += 1;
+        unique = format!("{stem}-{n}{ext}");
+    }
+    unique
+}
+
+fn supplements_size(val: &Version) -> u64 {
+    val.supplements.iter().map(|supplement| supplement.size).sum()
+}
+
+fn within_limit(total: &mut u64, bytes: &[u8]) -> Result<(), DownloadsMsg> {
+    *total += bytes.len() as u64;
+    match *total > BUNDLE_LIMIT {
+        true => Err(DownloadsMsg::TooLarge),
+        false => Ok(()),
+    }
+}
+
+async fn bundle(cfg: &AppConfig, val: &Version) -> Result<Vec<u8>, DownloadsMsg> {
+    let mut total = supplements_size(val);
+    if total > BUNDLE_LIMIT {
+        return Err(DownloadsMsg::TooLarge);
+    }
+    let dir = format!("v{}.{}", val.major_num, val.minor_num);
+    let release = fetch(cfg, val.file_id).await?;
+    within_limit(&mut total, &release)?;
+    let mut files = vec![(format!("{dir}/release.pdf"), release)];
+    if let Some(source_id) = val.source_id {
+        let source = fetch(cfg, source_id).await?;
+        within_limit(&mut total, &source)?;
+        files.push((format!("{dir}/source{}", archive::extension(&source)), source));
+    }
+    let mut taken = BTreeSet::new();
+    for supplement in val.supplements.iter() {
+        let name = supplement_name(&supplement.name, supplement.file_id, &mut taken);
+        files.push((format!("{dir}/supplements/{name}"), fetch(cfg, supplement.file_id).await?));
+    }
+    Ok(archive::pack(
+        files.iter().map(|(path, content)| (path.as_str(), content.as_slice())),
+        val.uploaded_at.timestamp(),
+    ))
+}
+
+impl Component for Downloads {
+    type Message = DownloadsMsg;
+    type Properties = DownloadsProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self::default()
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            DownloadsMsg::Err(e) => {
+                self.bundling = false;
+                ctx.props().err.emit(e);
+                true
+            }
+            DownloadsMsg::Alert(other) => {
+                self.bundling = false;
+                ctx.props().alert.emit(other);
+                true
+            }
+            DownloadsMsg::Bundle => {
+                self.bundling = true;
+                ctx.link().send_future({
+                    let cfg = ctx.props().cfg.clone();
+                    let val = ctx.props().val.clone();
+                    async move {
+                        match bundle(&cfg, &val).await {
+                            Ok(data) => DownloadsMsg::Bundled(data),
+                            Err(msg) => msg,
+                        }
+                    }
+                });
+                true
+            }
+            DownloadsMsg::Bundled(data) => {
+                self.bundling = false;
+                let blob = gloo::file::Blob::new_with_options(data.as_slice(), Some("application/x-tar"));
+                self.bundle = Some(gloo::file::ObjectUrl::from(blob));
+                true
+            }
+            DownloadsMsg::TooLarge => {
+                self.bundling = false;
+                self.too_large = true;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let val = &props.val;
+        let supplements = val.supplements.iter().map(|supplement| {
+            let href = props
+                .cfg
+                .api
+                .join(&format!("files/{}", supplement.file_id.to_hex()))
+                .map(|href| href.to_string())
+                .unwrap_or_default();
+            let kind = match &*supplement.mime {
+                "" => "unknown type",
+                mime => mime,
+            };
+            html_nested! {
+                <li>
+                    <a {href} download={ supplement.name.to_string() }>{ &*supplement.name }</a>
+                    <small>{ format!(" ({}, {kind})", sizes::format(supplement.size)) }</small>
+                    if !supplement.description.is_empty() {
+                        <br />{ &*supplement.description }
+                    }
+                </li>
+            }
+        });
+        let name = format!("version-{}.{}.tar", val.major_num, val.minor_num);
+        let bundle = match self.bundle {
+            Some(ref url) => html!(<a href={ (**url).to_owned() } download={ name.clone() }>{ format!("Save {name}") }</a>),
+            None if self.bundling => html!({ "Collecting files..." }),
+            None if self.too_large || supplements_size(val) > BUNDLE_LIMIT => html!({
+                format!("These files are larger than {}, so they cannot be bundled in the browser. Download them one by one instead.", sizes::format(BUNDLE_LIMIT))
+            }),
+            None => {
+                let onclick = ctx.link().callback(|_| DownloadsMsg::Bundle);
+                html!(<button {onclick}>{ "Download all files" }</button>)
+            }
+        };
+        html! {
+            <section>
+                <h2>{ "Downloads" }</h2>
+                if !val.supplements.is_empty() {
+                    <h3>{ "Supplementary materials" }</h3>
+                    <ul>{ for supplements }</ul>
+                }
+                <p>
+                    { bundle }<br />
+                    <small>{ "A tar archive of the release PDF, the source archive and all supplementary materials of this version." }</small>
+                </p>
+            </section>
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bson::oid::ObjectId;
+
+    use super::*;
+
+    #[test]
+    fn supplement_names_are_unique_and_safe() {
+        let id = ObjectId::new();
+        let mut taken = BTreeSet::new();
+        let names = ["data.csv", "../data.csv", "data.csv", "data-2.csv", "", "..", "notes", "notes"]
+            .map(|name| supplement_name(name, id, &mut taken));
+        assert_eq!(
+            names.map(|name| name.replace(&id.to_hex(), "ID")),
+            ["data.csv", "data-2.csv", "data-3.csv", "data-2-2.csv", "ID", "ID-2", "notes", "notes-2"]
+        );
+    }
+}
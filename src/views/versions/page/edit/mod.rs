@@ -115,7 +115,8 @@ impl Component for Edit {
             }
         });
         html! {
-            <fieldset>
+            <fieldset id="edit">
+                <legend>{ "Edit" }</legend>
                 <p>
                     <button {onclick}>
                         { "Pass directly" }
@@ -11,9 +11,12 @@ use yew_router::scope_ext::RouterScopeExt;
 use crate::{models::{
     common::{AppConfig, AppError, FetchOther},
     version::Version,
-}, views::{Route, alerts::AlertBox}};
+}, views::{Route, alerts::AlertBox, route::Fragment, share::Share}};
 
+mod downloads;
 mod pdf;
+mod source;
+mod verify;
 
 #[derive(Default)]
 pub struct Page {
@@ -80,7 +83,7 @@ impl Component for Page {
         let val = ctx.props().val.to_owned();
         let review = ctx.props().review;
         let adjudge = ctx.props().adjudge;
-        let edit = ctx.props().review;
+        let edit = ctx.props().edit;
         let file = Rc::new(
             match cfg.api.join(&format!("files/{}", val.file_id.to_hex())) {
                 Ok(file) => file,
@@ -105,14 +108,27 @@ impl Component for Page {
                     { "Version " }{ val.major_num }{ "." }{ val.minor_num }
                 </h1>
 
+                if !val.message.is_empty() {
+                    <blockquote>{ &*val.message }</blockquote>
+                }
+
                 <pdf::PDF cfg={ cfg.clone() } {file} />
 
+                <verify::Verify release={ val.release_sha256.clone() } source={ val.source_sha256.clone() } supplements={ val.supplements.clone() } />
+
                 if let Some(source) = source {
-                    <p>
-                        <a href={ source.to_string() }>{ "Source Code" }</a>
-                    </p>
+                    <source::Source err={ err.clone() } alert={ alert.clone() } href={ Rc::new(source) }
+                        limit={ cfg.limits.extracted() } />
                 }
 
+                <Share
+                    title={ format!("Version {}.{}", val.major_num, val.minor_num) }
+                    canonical={ val.thesis_url() }
+                    pinned={ Some(val.canonical_url()) }
+                />
+
+                <downloads::Downloads cfg={ cfg.clone() } err={ err.clone() } alert={ alert.clone() } val={ val.clone() } />
+
                 if review {
                     <review::Review cfg={ cfg.clone() } err={ err.clone() } id={ val._id } refresh={ home } />
                 }
@@ -129,4 +145,19 @@ impl Component for Page {
             </div>
         }
     }
+
+    fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        if first_render {
+            let hash = gloo::utils::window().location().hash().unwrap_or_default();
+            let fragment = ctx
+                .link()
+                .location()
+                .and_then(|location| location.state::<Fragment>())
+                .map(|fragment| fragment.0)
+                .or_else(|| hash.strip_prefix('#'));
+            if let Some(panel) = fragment.and_then(|id| gloo::utils::document().get_element_by_id(id)) {
+                panel.scroll_into_view();
+            }
+        }
+    }
 }
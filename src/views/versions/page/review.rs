@@ -4,7 +4,7 @@ use bson::oid::ObjectId;
 use web_sys::{HtmlInputElement, HtmlTextAreaElement, InputEvent, RequestCredentials};
 use yew::{html, AttrValue, Callback, Component, Context, Html, Properties, TargetCast};
 
-use crate::models::common::{AppConfig, AppError};
+use crate::{models::common::{AppConfig, AppError}, views::markup};
 
 #[derive(Default)]
 pub(super) struct Review {
@@ -106,7 +106,7 @@ impl Component for Review {
             }
         });
         html! {
-            <fieldset>
+            <fieldset id="review">
                 <legend>{ "Review" }</legend>
                 <label>
                     { "Judgement: " }
@@ -116,6 +116,7 @@ impl Component for Review {
                     { "Criticism: " }
                     <textarea oninput={ criticise } value={ self.criticism.to_owned() } />
                 </label>
+                { markup::preview(&self.criticism) }
                 <button disabled={ self.criticism.len() > 0 } {onclick} >
                     { "Submit" }
                 </button>
@@ -0,0 +1,180 @@
+use yew::{html, Html};
+
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+enum Token {
+    Plain,
+    Comment,
+    Command,
+    Math,
+    Entry,
+    Key,
+}
+
+fn push(tokens: &mut Vec<(Token, String)>, token: Token, c: char) {
+    match tokens.last_mut() {
+        Some((last, text)) if *last == token => text.push(c),
+        _ => tokens.push((token, c.to_string())),
+    }
+}
+
+fn tex(src: &str) -> Vec<(Token, String)> {
+    let mut tokens = Vec::new();
+    let mut chars = src.chars().peekable();
+    let mut math = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '%' => {
+                push(&mut tokens, Token::Comment, c);
+                while let Some(c) = chars.next_if(|c| *c != '\n') {
+                    push(&mut tokens, Token::Comment, c);
+                }
+            }
+            '\\' => {
+                tokens.push((Token::Command, c.to_string()));
+                match chars.next_if(|c| c.is_ascii_alphabetic() || c == &'@') {
+                    Some(c) => {
+                        push(&mut tokens, Token::Command, c);
+                        while let Some(c) = chars.next_if(|c| c.is_ascii_alphabetic() || c == &'@') {
+                            push(&mut tokens, Token::Command, c);
+                        }
+                    }
+                    None => {
+                        if let Some(c) = chars.next() {
+                            push(&mut tokens, Token::Command, c);
+                        }
+                    }
+                }
+            }
+            '$' => {
+                push(&mut tokens, Token::Math, c);
+                if chars.next_if_eq(&'$').is_some() {
+                    push(&mut tokens, Token::Math, '$');
+                }
+                math = !math;
+            }
+            c if math => push(&mut tokens, Token::Math, c),
+            c => push(&mut tokens, Token::Plain, c),
+        }
+    }
+    tokens
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || "_-:.".contains(c)
+}
+
+fn bib(src: &str) -> Vec<(Token, String)> {
+    let mut tokens = Vec::new();
+    let mut chars = src.chars().peekable();
+    let mut depth = 0usize;
+    while let Some(c) = chars.next() {
+        match c {
+            '@' if depth == 0 => {
+                push(&mut tokens, Token::Entry, c);
+                while let Some(c) = chars.next_if(|c| c.is_ascii_alphabetic()) {
+                    push(&mut tokens, Token::Entry, c);
+                }
+            }
+            '%' if depth == 0 => {
+                push(&mut tokens, Token::Comment, c);
+                while let Some(c) = chars.next_if(|c| *c != '\n') {
+                    push(&mut tokens, Token::Comment, c);
+                }
+            }
+            c if depth == 1 && is_word(c) => {
+                let mut word = c.to_string();
+                while let Some(c) = chars.next_if(|c| is_word(*c)) {
+                    word.push(c);
+                }
+                let token = match chars.clone().find(|c| !c.is_whitespace()) {
+                    Some('=') => Token::Key,
+                    _ => Token::Plain,
+                };
+                for c in word.chars() {
+                    push(&mut tokens, token, c);
+                }
+            }
+            c => {
+                match c {
+                    '{' | '(' => depth += 1,
+                    '}' | ')' => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+                push(&mut tokens, Token::Plain, c);
+            }
+        }
+    }
+    tokens
+}
+
+pub(super) fn view(name: &str, src: &str) -> Option<Html> {
+    let extension = name.rsplit_once('.').map(|(_, ext)| ext.to_ascii_lowercase())?;
+    let tokens = match extension.as_str() {
+        "tex" | "sty" | "cls" | "ltx" | "bbl" | "dtx" => tex(src),
+        "bib" => bib(src),
+        "txt" | "md" | "bst" | "cfg" | "log" => vec![(Token::Plain, src.to_owned())],
+        _ => return None,
+    };
+    let spans = tokens.into_iter().map(|(token, text)| match token {
+        Token::Plain => html!({ text }),
+        Token::Comment => html!(<i class="comment">{ text }</i>),
+        Token::Command => html!(<b class="command">{ text }</b>),
+        Token::Math => html!(<var class="math">{ text }</var>),
+        Token::Entry => html!(<b class="entry">{ text }</b>),
+        Token::Key => html!(<em class="key">{ text }</em>),
+    });
+    Some(html!(<pre><code>{ for spans }</code></pre>))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens<const N: usize>(expected: [(Token, &str); N]) -> Vec<(Token, String)> {
+        expected.into_iter().map(|(t, s)| (t, s.to_owned())).collect()
+    }
+
+    #[test]
+    fn tex_commands_comments_and_math() {
+        assert_eq!(
+            tex("\\section{Intro} % todo\n$x^2$ and \\%"),
+            tokens([
+                (Token::Command, "\\section"),
+                (Token::Plain, "{Intro} "),
+                (Token::Comment, "% todo"),
+                (Token::Plain, "\n"),
+                (Token::Math, "$x^2$"),
+                (Token::Plain, " and "),
+                (Token::Command, "\\%"),
+            ])
+        );
+    }
+
+    #[test]
+    fn tex_display_math_and_adjacent_commands() {
+        assert_eq!(
+            tex("$$a$$\\alpha\\beta"),
+            tokens([(Token::Math, "$$a$$"), (Token::Command, "\\alpha"), (Token::Command, "\\beta")])
+        );
+    }
+
+    #[test]
+    fn bib_entries_and_keys() {
+        assert_eq!(
+            bib("@article{knuth84,\n  title = {Literate {P}rogramming}\n}"),
+            tokens([
+                (Token::Entry, "@article"),
+                (Token::Plain, "{knuth84,\n  "),
+                (Token::Key, "title"),
+                (Token::Plain, " = {Literate {P}rogramming}\n}"),
+            ])
+        );
+    }
+
+    #[test]
+    fn bib_comments_outside_entries() {
+        assert_eq!(bib("% refs\n"), tokens([(Token::Comment, "% refs"), (Token::Plain, "\n")]));
+    }
+}
@@ -0,0 +1,188 @@
+use std::{collections::BTreeMap, rc::Rc};
+
+use web_sys::RequestCredentials;
+use yew::{html, html_nested, Callback, Component, Context, Html, Properties};
+
+use crate::models::{
+    archive::{Archive, ArchiveError, EntryKind},
+    common::{AppError, FetchOther, FetchRes},
+};
+
+mod highlight;
+
+#[derive(Default)]
+struct Node<'a> {
+    entry: Option<usize>,
+    children: BTreeMap<&'a str, Node<'a>>,
+}
+
+#[derive(Default)]
+pub(super) struct Source {
+    archive: Option<Result<Rc<Archive>, ArchiveError>>,
+    loading: bool,
+    selected: Option<(usize, gloo::file::ObjectUrl)>,
+}
+
+pub(super) enum SourceMsg {
+    Err(AppError),
+    Alert(FetchOther),
+    Browse,
+    Loaded(Result<Archive, ArchiveError>),
+    Select(usize),
+}
+
+#[derive(PartialEq, Properties)]
+pub(super) struct SourceProps {
+    pub(super) err: Callback<AppError>,
+    pub(super) alert: Callback<FetchOther>,
+    pub(super) href: Rc<url::Url>,
+    pub(super) limit: u64,
+}
+
+impl Source {
+    fn view_node(ctx: &Context<Self>, archive: &Archive, name: &str, node: &Node) -> Html {
+        let children = node
+            .children
+            .iter()
+            .map(|(name, child)| Self::view_node(ctx, archive, name, child));
+        match node.entry.map(|i| (i, &archive.entries[i])) {
+            Some((i, entry)) if entry.kind == EntryKind::File => {
+                let onclick = ctx.link().callback(move |_| SourceMsg::Select(i));
+                html! {
+                    <li>
+                        <button {onclick}>{ name }</button>
+                        { format!(" {} bytes", entry.size) }
+                    </li>
+                }
+            }
+            Some((_, entry)) if entry.kind == EntryKind::Link => html!(<li>{ name }{ " (link)" }</li>),
+            _ => html! {
+                <li>
+                    <details open={ true }>
+                        <summary>{ name }{ "/" }</summary>
+                        <ul>{ for children }</ul>
+                    </details>
+                </li>
+            },
+        }
+    }
+
+    fn view_preview(&self, archive: &Archive) -> Html {
+        let (i, href) = match self.selected {
+            Some((i, ref href)) => (i, href),
+            None => return html!(<p>{ "Select a file to preview it." }</p>),
+        };
+        let entry = &archive.entries[i];
+        let name = entry.path.rsplit('/').next().unwrap_or_default();
+        let preview = std::str::from_utf8(archive.content(entry))
+            .ok()
+            .and_then(|src| highlight::view(name, src))
+            .unwrap_or_else(|| html!(<p>{ "No preview is available for this file." }</p>));
+        html! {
+            <section>
+                <h3>{ &*entry.path }</h3>
+                <p>
+                    <a href={ href.to_string() } download={ name.to_owned() }>{ "Download file" }</a>
+                </p>
+                { preview }
+            </section>
+        }
+    }
+}
+
+impl Component for Source {
+    type Message = SourceMsg;
+    type Properties = SourceProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self::default()
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            SourceMsg::Err(e) => {
+                self.loading = false;
+                ctx.props().err.emit(e);
+                true
+            }
+            SourceMsg::Alert(other) => {
+                self.loading = false;
+                ctx.props().alert.emit(other);
+                true
+            }
+            SourceMsg::Browse => {
+                self.loading = true;
+                ctx.link().send_future({
+                    let href = ctx.props().href.clone();
+                    let limit = ctx.props().limit;
+                    async move {
+                        let res = <FetchRes<Vec<u8>>>::try_binary_from_gloo_res(
+                            gloo::net::http::Request::get(href.as_str())
+                                .credentials(RequestCredentials::Include)
+                                .send()
+                                .await,
+                        )
+                        .await;
+                        match res {
+                            Ok(FetchRes::Body(bytes)) => SourceMsg::Loaded(Archive::open(&bytes, limit)),
+                            Ok(FetchRes::Other(other)) => SourceMsg::Alert(other),
+                            Err(e) => SourceMsg::Err(e),
+                        }
+                    }
+                });
+                true
+            }
+            SourceMsg::Loaded(archive) => {
+                self.loading = false;
+                self.archive = Some(archive.map(Rc::new));
+                true
+            }
+            SourceMsg::Select(i) => {
+                if let Some(Ok(ref archive)) = self.archive {
+                    let blob = gloo::file::Blob::new(archive.content(&archive.entries[i]));
+                    self.selected = Some((i, gloo::file::ObjectUrl::from(blob)));
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let browser = match self.archive {
+            None if self.loading => html!(<p>{ "Decompressing source archive..." }</p>),
+            None => {
+                let onclick = ctx.link().callback(|_| SourceMsg::Browse);
+                html!(<button {onclick}>{ "Browse files" }</button>)
+            }
+            Some(Err(ref e)) => html!(<p>{ e.to_string() }</p>),
+            Some(Ok(ref archive)) => {
+                let mut root = Node::default();
+                for (i, entry) in archive.entries.iter().enumerate() {
+                    let node = entry
+                        .path
+                        .split('/')
+                        .fold(&mut root, |node, part| node.children.entry(part).or_default());
+                    node.entry = Some(i);
+                }
+                let items = root.children.iter().map(|(name, node)| {
+                    html_nested!(<>{ Self::view_node(ctx, archive, name, node) }</>)
+                });
+                html! {
+                    <>
+                        <ul>{ for items }</ul>
+                        { self.view_preview(archive) }
+                    </>
+                }
+            }
+        };
+        html! {
+            <section>
+                <h2>{ "Source" }</h2>
+                <p>
+                    <a href={ ctx.props().href.to_string() }>{ "Download source archive" }</a>
+                </p>
+                { browser }
+            </section>
+        }
+    }
+}
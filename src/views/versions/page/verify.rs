@@ -0,0 +1,144 @@
+use std::rc::Rc;
+
+use gloo::file::File;
+use web_sys::{DragEvent, Event, HtmlInputElement};
+use yew::{html, Component, Context, Html, Properties, TargetCast};
+
+use crate::models::{digest, version::Supplement};
+
+#[derive(Default)]
+pub(super) struct Verify {
+    generation: u32,
+    checking: Option<String>,
+    result: Option<(String, Result<String, String>)>,
+}
+
+pub(super) enum VerifyMsg {
+    Check(Option<File>),
+    Checked(u32, String, Result<String, String>),
+}
+
+#[derive(PartialEq, Properties)]
+pub(super) struct VerifyProps {
+    pub(super) release: Option<Rc<str>>,
+    pub(super) source: Option<Rc<str>>,
+    pub(super) supplements: Rc<Vec<Supplement>>,
+}
+
+impl Component for Verify {
+    type Message = VerifyMsg;
+    type Properties = VerifyProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self::default()
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            VerifyMsg::Check(Some(file)) => {
+                self.generation += 1;
+                self.checking = Some(file.name());
+                self.result = None;
+                let generation = self.generation;
+                ctx.link().send_future(async move {
+                    let checked = digest::sha256(&file).await.map_err(|e| e.to_string());
+                    VerifyMsg::Checked(generation, file.name(), checked)
+                });
+                true
+            }
+            VerifyMsg::Check(None) => false,
+            VerifyMsg::Checked(generation, _, _) if generation != self.generation => false,
+            VerifyMsg::Checked(_, name, checked) => {
+                self.checking = None;
+                self.result = Some((name, checked));
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let expected = [("release file".to_owned(), &props.release), ("source file".to_owned(), &props.source)]
+            .into_iter()
+            .chain(
+                props
+                    .supplements
+                    .iter()
+                    .map(|supplement| (format!("supplement {}", supplement.name), &supplement.sha256)),
+            )
+            .filter_map(|(kind, sha256)| Some((kind, sha256.as_deref()?)))
+            .collect::<Vec<_>>();
+        if expected.is_empty() {
+            return html!();
+        }
+        let ondragover = |e: DragEvent| e.prevent_default();
+        let ondrop = ctx.link().callback(|e: DragEvent| {
+            e.prevent_default();
+            VerifyMsg::Check(
+                e.data_transfer()
+                    .and_then(|d| d.files())
+                    .and_then(|f| f.get(0))
+                    .map(File::from),
+            )
+        });
+        let onchange = ctx.link().batch_callback(|e: Event| {
+            e.target_dyn_into::<HtmlInputElement>()
+                .map(|i| VerifyMsg::Check(i.files().and_then(|f| f.get(0)).map(File::from)))
+        });
+        let result = match (&self.checking, &self.result) {
+            (Some(name), _) => html!(<p>{ format!("Hashing {name}...") }</p>),
+            (None, Some((name, Ok(digest)))) => {
+                let matched = expected
+                    .iter()
+                    .find(|(_, expected)| *expected == digest.as_str())
+                    .map(|(kind, _)| kind);
+                match matched {
+                    Some(kind) => html!(<p>{ format!("✅ {name} is identical to the {kind} of this version.") }</p>),
+                    None => html! {
+                        <p>
+                            { format!("❎ {name} does not match any file of this version. Its SHA-256 is ") }
+                            <code>{ digest }</code>
+                        </p>
+                    },
+                }
+            }
+            (None, Some((name, Err(e)))) => html!(<p>{ format!("❎ {name} could not be read: {e}") }</p>),
+            (None, None) => html!(),
+        };
+        let supplements = props.supplements.iter().filter_map(|supplement| {
+            let sha256 = supplement.sha256.as_deref()?;
+            Some(html! {
+                <>
+                    <dt>{ format!("{} SHA-256", supplement.name) }</dt>
+                    <dd><code>{ sha256 }</code></dd>
+                </>
+            })
+        });
+        html! {
+            <section>
+                <h2>{ "Checksums" }</h2>
+                <dl>
+                    if let Some(ref release) = props.release {
+                        <dt>{ "Release SHA-256" }</dt>
+                        <dd><code>{ &**release }</code></dd>
+                    }
+                    if let Some(ref source) = props.source {
+                        <dt>{ "Source SHA-256" }</dt>
+                        <dd><code>{ &**source }</code></dd>
+                    }
+                    { for supplements }
+                </dl>
+                <fieldset {ondragover} {ondrop}>
+                    <legend>{ "Verify my local copy" }</legend>
+                    <p>{ "Drop a file here, or " }
+                        <label>
+                            { "choose one: " }
+                            <input type="file" {onchange} />
+                        </label>
+                    </p>
+                    { result }
+                </fieldset>
+            </section>
+        }
+    }
+}